@@ -0,0 +1,100 @@
+use core::{fmt::Debug, sync::atomic::Ordering};
+
+/// Unsigned flag types that have an atomic counterpart in the standard library.
+///
+/// This trait is used by the visited structs that can be shared across threads,
+/// and it is implemented for `u8`, `u16`, `u32`, `u64` and `usize` on the targets
+/// that support atomic operations of the corresponding width.
+pub trait AtomicFlag: Copy + PartialEq {
+    /// The atomic type that stores values of this flag type.
+    type Atomic: Debug + Send + Sync;
+
+    /// Returns a new atomic storing the provided value.
+    fn new_atomic(value: Self) -> Self::Atomic;
+
+    /// Returns the value currently stored in the provided atomic.
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+
+    /// Stores the provided value in the provided atomic.
+    fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+
+    /// Stores the provided value in the provided atomic, returning the previous one.
+    fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Stores the new value in the provided atomic if it currently holds the expected one.
+    fn compare_exchange(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self>;
+
+    /// Returns a mutable reference to the value stored in the provided atomic.
+    fn get_mut(atomic: &mut Self::Atomic) -> &mut Self;
+
+    /// Consumes the provided atomic and returns the value it stores.
+    fn into_inner(atomic: Self::Atomic) -> Self;
+}
+
+macro_rules! impl_atomic_flag {
+    ($($flag:ty => $atomic:ty),*) => {
+        $(
+            impl AtomicFlag for $flag {
+                type Atomic = $atomic;
+
+                #[inline(always)]
+                fn new_atomic(value: Self) -> Self::Atomic {
+                    <$atomic>::new(value)
+                }
+
+                #[inline(always)]
+                fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+                    atomic.load(order)
+                }
+
+                #[inline(always)]
+                fn store(atomic: &Self::Atomic, value: Self, order: Ordering) {
+                    atomic.store(value, order)
+                }
+
+                #[inline(always)]
+                fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.swap(value, order)
+                }
+
+                #[inline(always)]
+                fn compare_exchange(
+                    atomic: &Self::Atomic,
+                    current: Self,
+                    new: Self,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<Self, Self> {
+                    atomic.compare_exchange(current, new, success, failure)
+                }
+
+                #[inline(always)]
+                fn get_mut(atomic: &mut Self::Atomic) -> &mut Self {
+                    atomic.get_mut()
+                }
+
+                #[inline(always)]
+                fn into_inner(atomic: Self::Atomic) -> Self {
+                    atomic.into_inner()
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(target_has_atomic = "8")]
+impl_atomic_flag!(u8 => core::sync::atomic::AtomicU8);
+#[cfg(target_has_atomic = "16")]
+impl_atomic_flag!(u16 => core::sync::atomic::AtomicU16);
+#[cfg(target_has_atomic = "32")]
+impl_atomic_flag!(u32 => core::sync::atomic::AtomicU32);
+#[cfg(target_has_atomic = "64")]
+impl_atomic_flag!(u64 => core::sync::atomic::AtomicU64);
+#[cfg(target_has_atomic = "ptr")]
+impl_atomic_flag!(usize => core::sync::atomic::AtomicUsize);
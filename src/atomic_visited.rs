@@ -0,0 +1,186 @@
+use core::{ops::AddAssign, sync::atomic::Ordering};
+
+use alloc::vec::Vec;
+use num_traits::{bounds::UpperBounded, One, Zero};
+
+use crate::{
+    atomic::AtomicFlag,
+    epoch::bump_epoch,
+    index::VisitedIndex,
+    visited::{ResetStrategy, Visited},
+    visited_set::VisitedSet,
+};
+
+#[derive(Debug)]
+/// Visited struct whose values can be claimed by exactly one thread per epoch.
+///
+/// Differently from [`ConcurrentVisited`](crate::prelude::ConcurrentVisited), marking
+/// a value through [`AtomicVisited::try_claim`] is done with a single compare-exchange,
+/// so that when multiple threads try to claim the same index only one of them succeeds.
+/// This is what is needed, for instance, to assign parents deterministically in a
+/// parallel BFS.
+pub struct AtomicVisited<T: AtomicFlag> {
+    visited: Vec<T::Atomic>,
+    visited_flag: T,
+    // Strategy of the visited struct this was converted from, restored when converting back.
+    reset_strategy: ResetStrategy,
+}
+
+impl<T> AtomicVisited<T>
+where
+    T: AtomicFlag + Zero + One + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    /// Creates new zeroed atomic visited struct with given capacity.
+    pub fn zero(capacity: usize) -> Self {
+        Self {
+            visited: (0..capacity).map(|_| T::new_atomic(T::zero())).collect(),
+            visited_flag: T::one(),
+            reset_strategy: ResetStrategy::Full,
+        }
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was already visited.
+    pub fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        T::load(&self.visited[index.into_index()], Ordering::Acquire) == self.visited_flag
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited.
+    pub fn set_visited<U>(&self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        T::store(
+            &self.visited[index.into_index()],
+            self.visited_flag,
+            Ordering::Release,
+        );
+    }
+
+    #[inline(always)]
+    /// Tries to set the value at provided index as visited, returning whether this call did so.
+    ///
+    /// # Implementative details
+    /// Within an epoch, values may only ever be changed into the visited flag,
+    /// so a single compare-exchange against the previously loaded value is enough:
+    /// if it fails, some other thread has already claimed the index.
+    pub fn try_claim<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        let value = &self.visited[index.into_index()];
+        let current = T::load(value, Ordering::Acquire);
+        current != self.visited_flag
+            && T::compare_exchange(
+                value,
+                current,
+                self.visited_flag,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            )
+            .is_ok()
+    }
+
+    #[inline(always)]
+    /// Clears all visited values.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`], as the same wrap-around reset applies.
+    pub fn clear(&mut self) {
+        if bump_epoch(&mut self.visited_flag) {
+            self.reset();
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Zeroes all the values.
+    fn reset(&mut self) {
+        self.visited.iter_mut().for_each(|v| {
+            *T::get_mut(v) = T::zero();
+        });
+    }
+}
+
+impl<T: AtomicFlag + Zero> From<Visited<T>> for AtomicVisited<T> {
+    fn from(visited: Visited<T>) -> Self {
+        // Values from previous iterations are zeroed, as depending on the
+        // reset strategy of the visited struct they may exceed the flag.
+        let (visited, visited_flag, reset_strategy) = visited.into_parts();
+        Self {
+            visited: visited
+                .into_iter()
+                .map(|v| T::new_atomic(if v == visited_flag { v } else { T::zero() }))
+                .collect(),
+            visited_flag,
+            reset_strategy,
+        }
+    }
+}
+
+impl<T: AtomicFlag + Zero> From<AtomicVisited<T>> for Visited<T> {
+    fn from(visited: AtomicVisited<T>) -> Self {
+        // Values from previous iterations are zeroed, so that the reset
+        // strategy of the original visited struct can be restored.
+        let visited_flag = visited.visited_flag;
+        Self::from_parts(
+            visited
+                .visited
+                .into_iter()
+                .map(T::into_inner)
+                .map(|v| if v == visited_flag { v } else { T::zero() })
+                .collect(),
+            visited_flag,
+            visited.reset_strategy,
+        )
+    }
+}
+
+impl<T> VisitedSet for AtomicVisited<T>
+where
+    T: AtomicFlag + Zero + One + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        AtomicVisited::is_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        AtomicVisited::set_visited(self, index);
+    }
+
+    #[inline(always)]
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        !self.try_claim(index)
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        AtomicVisited::clear(self);
+    }
+
+    #[inline(always)]
+    fn len(&self) -> Option<usize> {
+        Some(self.visited.len())
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.visited.len()
+    }
+}
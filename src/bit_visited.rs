@@ -0,0 +1,178 @@
+use core::ops::AddAssign;
+
+use alloc::{vec, vec::Vec};
+use num_traits::{bounds::UpperBounded, One, Zero};
+
+use crate::{epoch::bump_epoch, index::VisitedIndex, visited_set::VisitedSet};
+
+#[derive(Clone, Debug)]
+/// Visited struct storing a single bit per value.
+///
+/// The values are split into blocks of 64, each with a bitmask and an epoch
+/// stamp. A block whose stamp differs from the current epoch is treated as
+/// empty, and it is lazily re-initialized the first time it is written in
+/// the current epoch. This takes `1 + size_of::<T>() / 8` bits per value,
+/// i.e. `1.125` bits with `u8` stamps.
+pub struct BitVisited<T> {
+    bits: Vec<u64>,
+    stamps: Vec<T>,
+    epoch: T,
+    len: usize,
+}
+
+impl<T> BitVisited<T>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    /// Creates new zeroed bit visited struct with given capacity.
+    pub fn zero(capacity: usize) -> Self {
+        let number_of_blocks = capacity.div_ceil(64);
+        Self {
+            bits: vec![0; number_of_blocks],
+            stamps: vec![T::zero(); number_of_blocks],
+            epoch: T::one(),
+            len: capacity,
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the number of values in the visited struct.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    /// Returns whether the visited struct holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    /// Returns the provided index as an `usize`, checking that it is within the length of the visited struct.
+    fn index<U>(&self, index: U) -> usize
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        assert!(
+            index < self.len,
+            "index {} is out of bounds for visited struct with length {}",
+            index,
+            self.len
+        );
+        index
+    }
+
+    #[inline(always)]
+    /// Returns the bitmask of the block containing the provided index, re-initializing it if stale.
+    fn block_mut(&mut self, index: usize) -> &mut u64 {
+        let block = index >> 6;
+        if self.stamps[block] != self.epoch {
+            self.stamps[block] = self.epoch.clone();
+            self.bits[block] = 0;
+        }
+        &mut self.bits[block]
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was already visited.
+    pub fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        let index = self.index(index);
+        let block = index >> 6;
+        self.stamps[block] == self.epoch && self.bits[block] & (1 << (index & 63)) != 0
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited.
+    pub fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        let index = self.index(index);
+        *self.block_mut(index) |= 1 << (index & 63);
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited and returns the previous value.
+    pub fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        let index = self.index(index);
+        let mask = 1 << (index & 63);
+        let bits = self.block_mut(index);
+        let visited = *bits & mask != 0;
+        *bits |= mask;
+        visited
+    }
+
+    #[inline(always)]
+    /// Clears all visited values.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`](crate::prelude::Visited::clear): the epoch is
+    /// bumped by one, and when it reaches the maximal value only the stamps,
+    /// one every 64 values, need to be zeroed.
+    pub fn clear(&mut self) {
+        if bump_epoch(&mut self.epoch) {
+            self.reset();
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Zeroes all the stamps.
+    fn reset(&mut self) {
+        self.stamps.iter_mut().for_each(|stamp| {
+            *stamp = T::zero();
+        });
+    }
+}
+
+impl<T> VisitedSet for BitVisited<T>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        BitVisited::is_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        BitVisited::set_visited(self, index);
+    }
+
+    #[inline(always)]
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        BitVisited::set_and_get_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        BitVisited::clear(self);
+    }
+
+    #[inline(always)]
+    fn len(&self) -> Option<usize> {
+        Some(BitVisited::len(self))
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        BitVisited::len(self)
+    }
+}
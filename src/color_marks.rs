@@ -0,0 +1,122 @@
+use core::ops::{AddAssign, Sub};
+
+use alloc::{vec, vec::Vec};
+use num_traits::{bounds::UpperBounded, One, Zero};
+
+use crate::{hints::unlikely, index::VisitedIndex};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// Color of a value in a depth-first search.
+pub enum Color {
+    #[default]
+    /// The value was not discovered yet.
+    White,
+    /// The value was discovered, but not all of its descendants were explored yet.
+    Gray,
+    /// The value and all of its descendants were explored.
+    Black,
+}
+
+#[derive(Clone, Debug)]
+/// Tri-state marks, as used in depth-first searches for cycle detection and topological sorting.
+///
+/// Each round of the search reserves two consecutive epoch values, the even
+/// `base` for gray values and `base + 1` for black values, while any other
+/// value is white, so moving the base two values up whitens all of them.
+pub struct ColorMarks<T> {
+    colors: Vec<T>,
+    base: T,
+}
+
+impl<T> ColorMarks<T>
+where
+    T: Zero + One + Copy + PartialOrd + UpperBounded + AddAssign + Sub<Output = T>,
+{
+    #[inline(always)]
+    /// Returns the base of the first round, i.e. two.
+    fn first_base() -> T {
+        T::one() + T::one()
+    }
+
+    #[inline(always)]
+    /// Creates new white color marks with given capacity.
+    pub fn zero(capacity: usize) -> Self {
+        Self {
+            colors: vec![T::zero(); capacity],
+            base: Self::first_base(),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the number of values in the color marks.
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    #[inline(always)]
+    /// Returns whether the color marks hold no values.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    #[inline(always)]
+    /// Returns the color of the value at given index.
+    pub fn color<U>(&self, index: U) -> Color
+    where
+        U: VisitedIndex,
+    {
+        let value = self.colors[index.into_index()];
+        if value == self.base {
+            Color::Gray
+        } else if value == self.base + T::one() {
+            Color::Black
+        } else {
+            Color::White
+        }
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as gray.
+    pub fn set_gray<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        self.colors[index.into_index()] = self.base;
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as black.
+    pub fn set_black<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        self.colors[index.into_index()] = self.base + T::one();
+    }
+
+    #[inline(always)]
+    /// Sets all the values as white.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`](crate::prelude::Visited::clear): the base is
+    /// bumped by two, and when there is no room left for both the gray and
+    /// the black values of the next round, all the values are zeroed and
+    /// the base goes back to two. As zero and one are never used as a base,
+    /// the zeroed values are white in every following round.
+    pub fn clear(&mut self) {
+        if unlikely(T::max_value() - self.base < Self::first_base() + T::one()) {
+            self.reset();
+        } else {
+            self.base += Self::first_base();
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Resets the base and zeroes all the values.
+    fn reset(&mut self) {
+        self.base = Self::first_base();
+        self.colors.iter_mut().for_each(|value| {
+            *value = T::zero();
+        });
+    }
+}
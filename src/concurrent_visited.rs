@@ -0,0 +1,183 @@
+use core::{ops::AddAssign, sync::atomic::Ordering};
+
+use alloc::vec::Vec;
+use num_traits::{bounds::UpperBounded, One, Zero};
+
+use crate::{
+    atomic::AtomicFlag,
+    epoch::bump_epoch,
+    index::VisitedIndex,
+    visited::{ResetStrategy, Visited},
+    visited_set::VisitedSet,
+};
+
+#[derive(Debug)]
+/// Visited struct that can be marked concurrently by multiple threads.
+///
+/// Values are stored in atomics and accessed with relaxed ordering, so that
+/// the struct can be shared across threads at the cost of plain memory accesses.
+/// Marking a value does not synchronize with the threads that later observe it
+/// as visited, so any data written alongside the mark may not be visible to them.
+pub struct ConcurrentVisited<T: AtomicFlag> {
+    visited: Vec<T::Atomic>,
+    visited_flag: T,
+    // Strategy of the visited struct this was converted from, restored when converting back.
+    reset_strategy: ResetStrategy,
+}
+
+impl<T> ConcurrentVisited<T>
+where
+    T: AtomicFlag + Zero + One + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    /// Creates new zeroed concurrent visited struct with given capacity.
+    pub fn zero(capacity: usize) -> Self {
+        Self {
+            visited: (0..capacity).map(|_| T::new_atomic(T::zero())).collect(),
+            visited_flag: T::one(),
+            reset_strategy: ResetStrategy::Full,
+        }
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was already visited.
+    pub fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        T::load(&self.visited[index.into_index()], Ordering::Relaxed) == self.visited_flag
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited.
+    pub fn set_visited<U>(&self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        T::store(
+            &self.visited[index.into_index()],
+            self.visited_flag,
+            Ordering::Relaxed,
+        );
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited and returns the previous value.
+    ///
+    /// # Implementative details
+    /// The previous value is read and the new one is written with a single
+    /// relaxed swap, so the previous value is always accurate, but the
+    /// marking establishes no ordering with the other memory operations of
+    /// the threads. When the thread that marks an index must also publish
+    /// data to the others, use [`AtomicVisited`](crate::prelude::AtomicVisited).
+    pub fn set_and_get_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        T::swap(
+            &self.visited[index.into_index()],
+            self.visited_flag,
+            Ordering::Relaxed,
+        ) == self.visited_flag
+    }
+
+    #[inline(always)]
+    /// Clears all visited values.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`], as the same wrap-around reset applies.
+    /// Since this method requires a mutable reference, no thread can be
+    /// marking values while the struct is being cleared.
+    pub fn clear(&mut self) {
+        if bump_epoch(&mut self.visited_flag) {
+            self.reset();
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Zeroes all the values.
+    fn reset(&mut self) {
+        self.visited.iter_mut().for_each(|v| {
+            *T::get_mut(v) = T::zero();
+        });
+    }
+}
+
+impl<T: AtomicFlag + Zero> From<Visited<T>> for ConcurrentVisited<T> {
+    fn from(visited: Visited<T>) -> Self {
+        // Values from previous iterations are zeroed, as depending on the
+        // reset strategy of the visited struct they may exceed the flag.
+        let (visited, visited_flag, reset_strategy) = visited.into_parts();
+        Self {
+            visited: visited
+                .into_iter()
+                .map(|v| T::new_atomic(if v == visited_flag { v } else { T::zero() }))
+                .collect(),
+            visited_flag,
+            reset_strategy,
+        }
+    }
+}
+
+impl<T: AtomicFlag + Zero> From<ConcurrentVisited<T>> for Visited<T> {
+    fn from(visited: ConcurrentVisited<T>) -> Self {
+        // Values from previous iterations are zeroed, so that the reset
+        // strategy of the original visited struct can be restored.
+        let visited_flag = visited.visited_flag;
+        Self::from_parts(
+            visited
+                .visited
+                .into_iter()
+                .map(T::into_inner)
+                .map(|v| if v == visited_flag { v } else { T::zero() })
+                .collect(),
+            visited_flag,
+            visited.reset_strategy,
+        )
+    }
+}
+
+impl<T> VisitedSet for ConcurrentVisited<T>
+where
+    T: AtomicFlag + Zero + One + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        ConcurrentVisited::is_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        ConcurrentVisited::set_visited(self, index);
+    }
+
+    #[inline(always)]
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        ConcurrentVisited::set_and_get_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        ConcurrentVisited::clear(self);
+    }
+
+    #[inline(always)]
+    fn len(&self) -> Option<usize> {
+        Some(self.visited.len())
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.visited.len()
+    }
+}
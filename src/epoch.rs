@@ -0,0 +1,29 @@
+//! Epoch bumping shared by all the epoch-stamped structs.
+//!
+//! All the structs of this crate clear their values by bumping an epoch,
+//! and only need to actually reset their values when the epoch wraps around.
+
+use core::ops::AddAssign;
+
+use num_traits::{bounds::UpperBounded, One};
+
+use crate::hints::unlikely;
+
+#[inline(always)]
+/// Bumps the provided epoch by one, returning whether it wrapped around.
+///
+/// When the epoch is at the maximal value, it is set back to one, and the
+/// caller must reset the values stamped with the previous epochs, which
+/// would otherwise be mistaken for values of the following ones.
+pub(crate) fn bump_epoch<T>(epoch: &mut T) -> bool
+where
+    T: One + PartialOrd + UpperBounded + AddAssign,
+{
+    if unlikely(*epoch == T::max_value()) {
+        *epoch = T::one();
+        true
+    } else {
+        *epoch += T::one();
+        false
+    }
+}
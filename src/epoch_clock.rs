@@ -0,0 +1,365 @@
+use core::{
+    cell::Cell,
+    fmt::{Debug, Formatter},
+    ops::AddAssign,
+};
+
+use alloc::{vec, vec::Vec};
+use num_traits::{bounds::UpperBounded, One, Zero};
+
+use crate::{
+    epoch::bump_epoch,
+    hints::unlikely,
+    index::VisitedIndex,
+    stamped_vec::{Entry, StampedVec},
+    visited_set::VisitedSet,
+};
+
+/// Epoch shared by multiple visited structs and stamped vectors.
+///
+/// Algorithms using several visited structs, such as a bidirectional search,
+/// may borrow a single clock from all of them, so that a single call to
+/// [`EpochClock::advance`] clears all of them at once. When the epoch wraps
+/// around, the clock bumps its generation, and each of the structs lazily
+/// resets its values the next time it is written.
+pub struct EpochClock<T> {
+    epoch: Cell<T>,
+    generation: Cell<usize>,
+}
+
+impl<T> EpochClock<T>
+where
+    T: Zero + One + Copy + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    /// Creates new epoch clock.
+    pub fn new() -> Self {
+        Self {
+            epoch: Cell::new(T::one()),
+            generation: Cell::new(0),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the current epoch.
+    pub fn epoch(&self) -> T {
+        self.epoch.get()
+    }
+
+    #[inline(always)]
+    /// Returns the number of times the epoch wrapped around.
+    pub fn generation(&self) -> usize {
+        self.generation.get()
+    }
+
+    #[inline(always)]
+    /// Clears all the visited structs and stamped vectors borrowing this clock.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`](crate::prelude::Visited::clear): the epoch is
+    /// bumped by one, and when it reaches the maximal value it is reset to one
+    /// and the generation is bumped, signalling to all the borrowing structs
+    /// that their values must be reset.
+    pub fn advance(&self) {
+        let mut epoch = self.epoch.get();
+        if bump_epoch(&mut epoch) {
+            self.generation.set(self.generation.get().wrapping_add(1));
+        }
+        self.epoch.set(epoch);
+    }
+}
+
+impl<T> Default for EpochClock<T>
+where
+    T: Zero + One + Copy + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for EpochClock<T>
+where
+    T: Copy + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EpochClock")
+            .field("epoch", &self.epoch.get())
+            .field("generation", &self.generation.get())
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+/// Visited struct whose visited flag is the epoch of a shared [`EpochClock`].
+pub struct ClockedVisited<'a, T> {
+    clock: &'a EpochClock<T>,
+    visited: Vec<T>,
+    generation: usize,
+}
+
+impl<'a, T> ClockedVisited<'a, T>
+where
+    T: Zero + One + Copy + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    /// Creates new zeroed visited struct with given capacity, borrowing the provided clock.
+    pub fn zero(clock: &'a EpochClock<T>, capacity: usize) -> Self {
+        Self {
+            clock,
+            visited: vec![T::zero(); capacity],
+            generation: clock.generation(),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the clock borrowed by the visited struct.
+    pub fn clock(&self) -> &'a EpochClock<T> {
+        self.clock
+    }
+
+    #[inline(always)]
+    /// Returns the number of values in the visited struct.
+    pub fn len(&self) -> usize {
+        self.visited.len()
+    }
+
+    #[inline(always)]
+    /// Returns whether the visited struct holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.visited.is_empty()
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was already visited.
+    pub fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.generation == self.clock.generation()
+            && self.visited[index.into_index()] == self.clock.epoch()
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited.
+    pub fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        self.synchronize();
+        self.visited[index.into_index()] = self.clock.epoch();
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited and returns the previous value.
+    pub fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.synchronize();
+        let epoch = self.clock.epoch();
+        core::mem::replace(&mut self.visited[index.into_index()], epoch) == epoch
+    }
+
+    #[inline(always)]
+    /// Resets the values if the epoch of the clock wrapped around since they were last written.
+    fn synchronize(&mut self) {
+        if unlikely(self.generation != self.clock.generation()) {
+            self.reset();
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Zeroes all the values and catches up with the generation of the clock.
+    fn reset(&mut self) {
+        self.generation = self.clock.generation();
+        self.visited.iter_mut().for_each(|v| {
+            *v = T::zero();
+        });
+    }
+}
+
+impl<T> VisitedSet for ClockedVisited<'_, T>
+where
+    T: Zero + One + Copy + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        ClockedVisited::is_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        ClockedVisited::set_visited(self, index);
+    }
+
+    #[inline(always)]
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        ClockedVisited::set_and_get_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        // The visited struct has no epoch of its own, so clearing it advances
+        // the shared clock, which also clears all the other borrowing structs.
+        self.clock.advance();
+    }
+
+    #[inline(always)]
+    fn len(&self) -> Option<usize> {
+        Some(ClockedVisited::len(self))
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        ClockedVisited::len(self)
+    }
+}
+
+impl<T> Debug for ClockedVisited<'_, T>
+where
+    T: Zero + One + Copy + PartialOrd + UpperBounded + AddAssign + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ClockedVisited")
+            .field("clock", self.clock)
+            .field("visited", &self.visited)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+/// Stamped vector whose epoch is the epoch of a shared [`EpochClock`].
+pub struct ClockedStampedVec<'a, V, T: Zero> {
+    clock: &'a EpochClock<T>,
+    values: StampedVec<V, T>,
+    generation: usize,
+}
+
+impl<'a, V, T> ClockedStampedVec<'a, V, T>
+where
+    T: Zero + One + Copy + PartialOrd + UpperBounded + AddAssign,
+{
+    /// Creates new empty stamped vector with given capacity, borrowing the provided clock.
+    pub fn zero(clock: &'a EpochClock<T>, capacity: usize) -> Self {
+        Self {
+            clock,
+            values: StampedVec::zero(capacity),
+            generation: clock.generation(),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the clock borrowed by the stamped vector.
+    pub fn clock(&self) -> &'a EpochClock<T> {
+        self.clock
+    }
+
+    #[inline(always)]
+    /// Returns the number of values in the stamped vector.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline(always)]
+    /// Returns whether the stamped vector holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was written in the current epoch.
+    pub fn contains<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.get(index).is_some()
+    }
+
+    #[inline(always)]
+    /// Returns a reference to the value at given index, if it was written in the current epoch.
+    pub fn get<U>(&self, index: U) -> Option<&V>
+    where
+        U: VisitedIndex,
+    {
+        if self.generation == self.clock.generation() {
+            self.values.get_at(index.into_index(), &self.clock.epoch())
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    /// Returns a mutable reference to the value at given index, if it was written in the current epoch.
+    pub fn get_mut<U>(&mut self, index: U) -> Option<&mut V>
+    where
+        U: VisitedIndex,
+    {
+        self.synchronize();
+        self.values.get_mut(index)
+    }
+
+    #[inline(always)]
+    /// Sets the value at given index, returning the previous one if it was written in the current epoch.
+    pub fn insert<U>(&mut self, index: U, value: V) -> Option<V>
+    where
+        U: VisitedIndex,
+    {
+        self.synchronize();
+        self.values.insert(index, value)
+    }
+
+    #[inline(always)]
+    /// Returns a mutable reference to the value at given index, writing it with the provided function if missing.
+    pub fn get_or_insert_with<U, F>(&mut self, index: U, f: F) -> &mut V
+    where
+        U: VisitedIndex,
+        F: FnOnce() -> V,
+    {
+        self.synchronize();
+        self.values.get_or_insert_with(index, f)
+    }
+
+    #[inline(always)]
+    /// Returns the entry at given index, for in-place manipulation.
+    pub fn entry<U>(&mut self, index: U) -> Entry<'_, V, T>
+    where
+        U: VisitedIndex,
+    {
+        self.synchronize();
+        self.values.entry(index)
+    }
+
+    #[inline(always)]
+    /// Aligns the epoch of the values with the one of the clock, resetting them if it wrapped around.
+    fn synchronize(&mut self) {
+        if unlikely(self.generation != self.clock.generation()) {
+            self.generation = self.clock.generation();
+            self.values.drop_values();
+        }
+        self.values.epoch = self.clock.epoch();
+    }
+}
+
+impl<V, T> Debug for ClockedStampedVec<'_, V, T>
+where
+    V: Debug,
+    T: Zero + One + Copy + PartialOrd + UpperBounded + AddAssign + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let values =
+            (0..self.len()).filter_map(|index| self.get(index).map(|value| (index, value)));
+        f.debug_map().entries(values).finish()
+    }
+}
@@ -0,0 +1,33 @@
+use core::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Errors returned by the checked methods of the visited structs.
+pub enum VisitedError {
+    /// The index is not smaller than the length of the visited struct.
+    IndexOutOfBounds {
+        /// The index that was requested.
+        index: usize,
+        /// The length of the visited struct.
+        len: usize,
+    },
+    /// The index cannot be converted into an `usize` without loss, as it happens for negative indices.
+    LossyIndexConversion,
+}
+
+impl Display for VisitedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            VisitedError::IndexOutOfBounds { index, len } => write!(
+                f,
+                "index {} is out of bounds for visited struct with length {}",
+                index, len
+            ),
+            VisitedError::LossyIndexConversion => {
+                write!(f, "index cannot be converted into an usize without loss")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VisitedError {}
@@ -0,0 +1,284 @@
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+    ops::AddAssign,
+};
+
+use alloc::{vec, vec::Vec};
+use num_traits::{bounds::UpperBounded, One, Zero};
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState;
+
+use crate::{epoch::bump_epoch, index::VisitedIndex, visited_set::VisitedSet};
+
+/// Minimum number of slots allocated by [`HashVisited`] once a key is set as visited.
+const MIN_SLOTS: usize = 8;
+
+#[derive(Clone, Debug)]
+/// Visited struct for sparse or non-integer keys, backed by an open addressing hash table.
+///
+/// Every slot of the table stores a key along with the epoch in which it
+/// was inserted. Slots from previous epochs are treated as empty and are
+/// reused by the following insertions, so the table is only rebuilt when it
+/// grows or when the epoch wraps around.
+pub struct HashVisited<K, T, S> {
+    keys: Vec<Option<K>>,
+    stamps: Vec<T>,
+    epoch: T,
+    len: usize,
+    hasher: S,
+}
+
+#[cfg(feature = "std")]
+impl<K, T> HashVisited<K, T, RandomState>
+where
+    K: Hash + Eq,
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    /// Creates new empty hash visited struct.
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+
+    #[inline(always)]
+    /// Creates new empty hash visited struct that can hold the given number of keys without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, T> Default for HashVisited<K, T, RandomState>
+where
+    K: Hash + Eq,
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, T, S> HashVisited<K, T, S>
+where
+    K: Hash + Eq,
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+    S: BuildHasher,
+{
+    #[inline(always)]
+    /// Creates new empty hash visited struct using the provided hasher.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self::with_capacity_and_hasher(0, hasher)
+    }
+
+    /// Creates new empty hash visited struct using the provided hasher, that can hold the given number of keys without reallocating.
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        let number_of_slots = if capacity == 0 {
+            0
+        } else {
+            (capacity + capacity.div_ceil(3))
+                .next_power_of_two()
+                .max(MIN_SLOTS)
+        };
+        Self {
+            keys: (0..number_of_slots).map(|_| None).collect(),
+            stamps: vec![T::zero(); number_of_slots],
+            epoch: T::one(),
+            len: 0,
+            hasher,
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the number of keys visited in the current iteration.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    /// Returns whether no key was visited in the current iteration.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    /// Returns the number of keys that can be visited in an iteration without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.keys.len() / 4 * 3
+    }
+
+    #[inline(always)]
+    /// Returns the slot where the probing for the provided key starts.
+    fn first_slot<Q>(&self, key: &Q) -> usize
+    where
+        Q: Hash + ?Sized,
+    {
+        self.hasher.hash_one(key) as usize & (self.keys.len() - 1)
+    }
+
+    /// Returns whether the provided key was already visited.
+    pub fn is_visited<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.len == 0 {
+            return false;
+        }
+        let mask = self.keys.len() - 1;
+        let mut slot = self.first_slot(key);
+        // Within an epoch slots are only ever turned into current ones, so all
+        // the slots probed before a key was inserted are still current: the
+        // first stale slot marks the end of the probing sequence.
+        while self.stamps[slot] == self.epoch {
+            if self.keys[slot].as_ref().map(Borrow::borrow) == Some(key) {
+                return true;
+            }
+            slot = (slot + 1) & mask;
+        }
+        false
+    }
+
+    #[inline(always)]
+    /// Sets the provided key as visited.
+    pub fn set_visited(&mut self, key: K) {
+        self.set_and_get_visited(key);
+    }
+
+    /// Sets the provided key as visited and returns whether it was already visited.
+    ///
+    /// # Implementative details
+    /// The table only grows when the key is not found and inserting it would
+    /// exceed the load factor, so visiting again the keys of the current
+    /// iteration never reallocates.
+    pub fn set_and_get_visited(&mut self, key: K) -> bool {
+        if !self.keys.is_empty() {
+            let mask = self.keys.len() - 1;
+            let mut slot = self.first_slot(&key);
+            while self.stamps[slot] == self.epoch {
+                if self.keys[slot].as_ref() == Some(&key) {
+                    return true;
+                }
+                slot = (slot + 1) & mask;
+            }
+            if (self.len + 1) * 4 <= self.keys.len() * 3 {
+                self.occupy(slot, key);
+                return false;
+            }
+        }
+        self.grow();
+        let slot = self.vacant_slot(&key);
+        self.occupy(slot, key);
+        false
+    }
+
+    #[inline(always)]
+    /// Stores the provided key in the provided slot, stamping it with the current epoch.
+    fn occupy(&mut self, slot: usize, key: K) {
+        self.stamps[slot] = self.epoch.clone();
+        self.keys[slot] = Some(key);
+        self.len += 1;
+    }
+
+    #[inline(always)]
+    /// Returns the first slot from a previous epoch in the probing sequence of the provided key.
+    fn vacant_slot(&self, key: &K) -> usize {
+        let mask = self.keys.len() - 1;
+        let mut slot = self.first_slot(key);
+        while self.stamps[slot] == self.epoch {
+            slot = (slot + 1) & mask;
+        }
+        slot
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Doubles the number of slots, moving the keys visited in the current iteration.
+    fn grow(&mut self) {
+        let number_of_slots = (self.keys.len() * 2).max(MIN_SLOTS);
+        let keys = core::mem::replace(&mut self.keys, (0..number_of_slots).map(|_| None).collect());
+        let stamps = core::mem::replace(&mut self.stamps, vec![T::zero(); number_of_slots]);
+        for (key, stamp) in keys.into_iter().zip(stamps) {
+            if stamp != self.epoch {
+                continue;
+            }
+            if let Some(key) = key {
+                let slot = self.vacant_slot(&key);
+                self.stamps[slot] = self.epoch.clone();
+                self.keys[slot] = Some(key);
+            }
+        }
+    }
+
+    #[inline(always)]
+    /// Clears all visited keys.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`](crate::prelude::Visited::clear): the epoch is
+    /// bumped by one, and when it reaches the maximal value all the stamps
+    /// are zeroed and the stale keys are dropped.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        if bump_epoch(&mut self.epoch) {
+            self.reset();
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Zeroes all the stamps and drops all the keys.
+    fn reset(&mut self) {
+        self.stamps.iter_mut().for_each(|stamp| {
+            *stamp = T::zero();
+        });
+        self.keys.iter_mut().for_each(|key| {
+            *key = None;
+        });
+    }
+}
+
+impl<T, S> VisitedSet for HashVisited<usize, T, S>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+    S: BuildHasher,
+{
+    #[inline(always)]
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        HashVisited::is_visited(self, &index.into_index())
+    }
+
+    #[inline(always)]
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        HashVisited::set_visited(self, index.into_index());
+    }
+
+    #[inline(always)]
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        HashVisited::set_and_get_visited(self, index.into_index())
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        HashVisited::clear(self);
+    }
+
+    #[inline(always)]
+    fn len(&self) -> Option<usize> {
+        None
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        HashVisited::capacity(self)
+    }
+}
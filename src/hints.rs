@@ -0,0 +1,15 @@
+//! Branch prediction hints.
+//!
+//! On stable Rust, [`unlikely`] is a no-op and the cold paths are instead
+//! expressed by moving them into `#[cold]` functions. When the `nightly`
+//! feature is enabled, the compiler intrinsics are used as well.
+
+#[cfg(feature = "nightly")]
+pub(crate) use core::intrinsics::unlikely;
+
+#[cfg(not(feature = "nightly"))]
+#[inline(always)]
+/// Hints that the provided condition is unlikely to be true.
+pub(crate) fn unlikely(condition: bool) -> bool {
+    condition
+}
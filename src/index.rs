@@ -0,0 +1,71 @@
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+/// Types that can be used to index the visited structs.
+///
+/// This trait is implemented for all the primitive integers and their `NonZero`
+/// counterparts. For newtypes wrapping a single index, such as `struct NodeId(u32)`,
+/// it can be derived with the `VisitedIndex` derive macro, available with the
+/// `derive` feature.
+pub trait VisitedIndex {
+    /// Returns the index as an `usize`, wrapping as an `as` cast would.
+    fn into_index(self) -> usize;
+
+    /// Returns the index as an `usize`, or `None` if it cannot be converted without loss.
+    fn try_into_index(self) -> Option<usize>;
+}
+
+macro_rules! impl_visited_index {
+    ($($index:ty),*) => {
+        $(
+            impl VisitedIndex for $index {
+                #[inline(always)]
+                fn into_index(self) -> usize {
+                    self as usize
+                }
+
+                #[inline(always)]
+                #[allow(clippy::useless_conversion)]
+                fn try_into_index(self) -> Option<usize> {
+                    usize::try_from(self).ok()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_visited_index_non_zero {
+    ($($index:ty),*) => {
+        $(
+            impl VisitedIndex for $index {
+                #[inline(always)]
+                fn into_index(self) -> usize {
+                    self.get().into_index()
+                }
+
+                #[inline(always)]
+                fn try_into_index(self) -> Option<usize> {
+                    self.get().try_into_index()
+                }
+            }
+        )*
+    };
+}
+
+impl_visited_index!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_visited_index_non_zero!(
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+);
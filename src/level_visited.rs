@@ -0,0 +1,176 @@
+use core::ops::{Add, AddAssign, Sub};
+
+use alloc::{vec, vec::Vec};
+use num_traits::{bounds::UpperBounded, FromPrimitive, One, ToPrimitive, Zero};
+
+use crate::{hints::unlikely, index::VisitedIndex};
+
+#[derive(Clone, Debug)]
+/// Visited struct also recording the level at which each value was visited, as in a BFS.
+///
+/// Each value stores `base + level`, where `base` is the smallest value of
+/// the current iteration, so that values smaller than the base are not
+/// visited. Clearing moves the base past the largest level written in the
+/// current iteration, so the vector is only zeroed once the remaining values
+/// of `T` cannot hold the levels of another iteration.
+pub struct LevelVisited<T> {
+    visited: Vec<T>,
+    base: T,
+    max_level: usize,
+}
+
+impl<T> LevelVisited<T>
+where
+    T: Zero
+        + One
+        + Copy
+        + PartialOrd
+        + UpperBounded
+        + AddAssign
+        + Add<Output = T>
+        + Sub<Output = T>
+        + FromPrimitive
+        + ToPrimitive,
+{
+    #[inline(always)]
+    /// Creates new zeroed level visited struct with given capacity.
+    pub fn zero(capacity: usize) -> Self {
+        Self {
+            visited: vec![T::zero(); capacity],
+            base: T::one(),
+            max_level: 0,
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the number of values in the visited struct.
+    pub fn len(&self) -> usize {
+        self.visited.len()
+    }
+
+    #[inline(always)]
+    /// Returns whether the visited struct holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.visited.is_empty()
+    }
+
+    #[inline(always)]
+    /// Returns the largest level that can be written without moving the base.
+    fn headroom(&self) -> usize {
+        (T::max_value() - self.base)
+            .to_usize()
+            .unwrap_or(usize::MAX)
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was already visited.
+    pub fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.visited[index.into_index()] >= self.base
+    }
+
+    #[inline(always)]
+    /// Returns the level of the value at given index, if it was already visited.
+    pub fn level<U>(&self, index: U) -> Option<usize>
+    where
+        U: VisitedIndex,
+    {
+        let value = self.visited[index.into_index()];
+        if value >= self.base {
+            (value - self.base).to_usize()
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the value encoding the provided level in the current iteration.
+    ///
+    /// # Panics
+    /// If the level cannot be represented with `T`, i.e. it is not smaller than `T::max_value()`.
+    fn encode(&mut self, level: usize) -> T {
+        if unlikely(level > self.headroom()) {
+            self.rebase();
+            assert!(
+                level <= self.headroom(),
+                "level {} cannot be represented by the level visited struct",
+                level
+            );
+        }
+        self.max_level = self.max_level.max(level);
+        // The level is not larger than the headroom, which is a value of `T`.
+        self.base + T::from_usize(level).unwrap()
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited at the provided level.
+    pub fn set_level<U>(&mut self, index: U, level: usize)
+    where
+        U: VisitedIndex,
+    {
+        let value = self.encode(level);
+        self.visited[index.into_index()] = value;
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited at the provided level and returns the previous level.
+    pub fn set_and_get_level<U>(&mut self, index: U, level: usize) -> Option<usize>
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        let value = self.encode(level);
+        let previous = self.level(index);
+        self.visited[index] = value;
+        previous
+    }
+
+    #[inline(always)]
+    /// Clears all visited values and their levels.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`](crate::prelude::Visited::clear): the base is
+    /// moved one past the largest value written in the current iteration,
+    /// and when there is no room left for it all the values are zeroed and
+    /// the base goes back to one.
+    pub fn clear(&mut self) {
+        if unlikely(self.max_level >= self.headroom()) {
+            self.reset();
+        } else {
+            self.base += T::from_usize(self.max_level).unwrap() + T::one();
+        }
+        self.max_level = 0;
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Moves the values of the current iteration down so that the base is one, zeroing all the others.
+    ///
+    /// # Implementative details
+    /// This is only needed when a level larger than the remaining headroom
+    /// is written in the middle of an iteration, as the current levels
+    /// cannot be discarded as in [`LevelVisited::clear`].
+    fn rebase(&mut self) {
+        let base = self.base;
+        self.visited.iter_mut().for_each(|v| {
+            *v = if *v >= base {
+                *v - base + T::one()
+            } else {
+                T::zero()
+            };
+        });
+        self.base = T::one();
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Resets the base and zeroes all the values.
+    fn reset(&mut self) {
+        self.base = T::one();
+        self.visited.iter_mut().for_each(|v| {
+            *v = T::zero();
+        });
+    }
+}
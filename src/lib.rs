@@ -1,6 +1,88 @@
-#![feature(core_intrinsics)]
+#![no_std]
+#![cfg_attr(feature = "nightly", feature(core_intrinsics))]
+#![cfg_attr(feature = "nightly", allow(internal_features))]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod atomic;
+#[cfg(feature = "alloc")]
+mod atomic_visited;
+#[cfg(feature = "alloc")]
+mod bit_visited;
+#[cfg(feature = "alloc")]
+mod color_marks;
+#[cfg(feature = "alloc")]
+mod concurrent_visited;
+mod epoch;
+#[cfg(feature = "alloc")]
+mod epoch_clock;
+mod error;
+#[cfg(feature = "alloc")]
+mod hash_visited;
+mod hints;
+mod index;
+#[cfg(feature = "alloc")]
+mod level_visited;
+#[cfg(feature = "alloc")]
+mod multi_visited;
+#[cfg(feature = "alloc")]
+mod recency_visited;
+#[cfg(feature = "alloc")]
+mod sparse_reset_visited;
+#[cfg(feature = "alloc")]
+mod stamped_columns;
+#[cfg(feature = "alloc")]
+mod stamped_vec;
+#[cfg(feature = "alloc")]
+mod tracked_visited;
+#[cfg(feature = "alloc")]
+mod visit_counter;
 mod visited;
+mod visited_set;
 
 pub mod prelude {
+    pub use crate::atomic::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::atomic_visited::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::bit_visited::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::color_marks::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::concurrent_visited::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::epoch_clock::*;
+    pub use crate::error::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::hash_visited::*;
+    pub use crate::index::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::level_visited::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::multi_visited::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::recency_visited::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::sparse_reset_visited::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::stamped_columns::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::stamped_vec::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::tracked_visited::*;
+    #[cfg(feature = "alloc")]
+    pub use crate::visit_counter::*;
     pub use crate::visited::*;
+    pub use crate::visited_set::*;
+    #[cfg(feature = "derive")]
+    pub use visited_rs_derive::{Stamped, VisitedIndex};
+}
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+/// Items used by the code generated by the derive macros.
+pub mod __private {
+    pub use alloc::vec::Vec;
 }
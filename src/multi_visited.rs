@@ -0,0 +1,124 @@
+use core::ops::AddAssign;
+
+use alloc::{vec, vec::Vec};
+use num_traits::{bounds::UpperBounded, One, Zero};
+
+use crate::{epoch::bump_epoch, index::VisitedIndex};
+
+#[derive(Clone, Debug)]
+/// Visited struct tracking `K` independent sets of visited values.
+///
+/// The values of the `K` lanes for a given index are stored next to each
+/// other, and all of them are compared against a single visited flag, so that
+/// for instance the two frontiers of a bidirectional search share the cache
+/// lines of each node, and are cleared together.
+pub struct MultiVisited<T, const K: usize> {
+    visited: Vec<[T; K]>,
+    visited_flag: T,
+}
+
+impl<T, const K: usize> MultiVisited<T, K>
+where
+    T: Zero + One + Copy + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    /// Creates new zeroed multi visited struct with given capacity.
+    pub fn zero(capacity: usize) -> Self {
+        Self {
+            visited: vec![[T::zero(); K]; capacity],
+            visited_flag: T::one(),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the number of values in each lane of the visited struct.
+    pub fn len(&self) -> usize {
+        self.visited.len()
+    }
+
+    #[inline(always)]
+    /// Returns whether the visited struct holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.visited.is_empty()
+    }
+
+    #[inline(always)]
+    /// Returns the number of lanes of the visited struct.
+    pub const fn lanes(&self) -> usize {
+        K
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was already visited in the provided lane.
+    ///
+    /// # Panics
+    /// If the lane is not smaller than `K` or the index is out of bounds.
+    pub fn is_visited<U>(&self, lane: usize, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.visited[index.into_index()][lane] == self.visited_flag
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited in the provided lane.
+    ///
+    /// # Panics
+    /// If the lane is not smaller than `K` or the index is out of bounds.
+    pub fn set_visited<U>(&mut self, lane: usize, index: U)
+    where
+        U: VisitedIndex,
+    {
+        self.visited[index.into_index()][lane] = self.visited_flag;
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited in the provided lane and returns the previous value.
+    ///
+    /// # Panics
+    /// If the lane is not smaller than `K` or the index is out of bounds.
+    pub fn set_and_get_visited<U>(&mut self, lane: usize, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        core::mem::replace(
+            &mut self.visited[index.into_index()][lane],
+            self.visited_flag,
+        ) == self.visited_flag
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was visited in all the lanes.
+    ///
+    /// In a bidirectional search with one lane per direction, this is
+    /// whether the two frontiers met at the provided index.
+    pub fn meet<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.visited[index.into_index()]
+            .iter()
+            .all(|value| *value == self.visited_flag)
+    }
+
+    #[inline(always)]
+    /// Clears all visited values of all the lanes.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`](crate::prelude::Visited::clear): the flag is
+    /// shared by all the lanes, so bumping it clears all of them at once.
+    pub fn clear(&mut self) {
+        if bump_epoch(&mut self.visited_flag) {
+            self.reset();
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Zeroes all the values of all the lanes.
+    fn reset(&mut self) {
+        self.visited.iter_mut().for_each(|values| {
+            *values = [T::zero(); K];
+        });
+    }
+}
@@ -0,0 +1,229 @@
+use core::ops::{AddAssign, Sub};
+
+use alloc::{vec, vec::Vec};
+use num_traits::{bounds::UpperBounded, FromPrimitive, One, ToPrimitive, Zero};
+
+use crate::{epoch::bump_epoch, index::VisitedIndex, visited_set::VisitedSet};
+
+#[derive(Clone, Debug)]
+/// Visited struct recording the epoch in which each value was last visited.
+///
+/// Besides whether a value was visited in the current epoch, this allows to
+/// ask whether it was visited in any of the last few epochs, as needed for
+/// instance by non-backtracking random walks and tabu searches. Each value
+/// stores the epoch of its last visit relative to an offset, and when the
+/// epoch reaches the maximal value of `T` the values are rebased rather
+/// than zeroed, so that the visits of the last `history` epochs survive.
+pub struct RecencyVisited<T> {
+    visited: Vec<T>,
+    epoch: T,
+    offset: u64,
+    history: T,
+}
+
+impl<T> RecencyVisited<T>
+where
+    T: Zero
+        + One
+        + Copy
+        + PartialOrd
+        + UpperBounded
+        + AddAssign
+        + Sub<Output = T>
+        + FromPrimitive
+        + ToPrimitive,
+{
+    #[inline(always)]
+    /// Creates new zeroed recency visited struct with given capacity, keeping half of the epochs of `T` as history.
+    pub fn zero(capacity: usize) -> Self {
+        let history = T::max_value().to_usize().unwrap_or(usize::MAX) / 2;
+        Self::with_history(capacity, history)
+    }
+
+    /// Creates new zeroed recency visited struct with given capacity, keeping the visits of the last `history` epochs across rebases.
+    ///
+    /// # Panics
+    /// If the history is not smaller than `T::max_value()`.
+    pub fn with_history(capacity: usize, history: usize) -> Self {
+        let history = T::from_usize(history)
+            .filter(|history| *history < T::max_value())
+            .unwrap_or_else(|| {
+                panic!(
+                    "history {} must be smaller than the maximal value of the visited flag",
+                    history
+                )
+            });
+        Self {
+            visited: vec![T::zero(); capacity],
+            epoch: T::one(),
+            offset: 0,
+            history,
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the number of values in the visited struct.
+    pub fn len(&self) -> usize {
+        self.visited.len()
+    }
+
+    #[inline(always)]
+    /// Returns whether the visited struct holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.visited.is_empty()
+    }
+
+    #[inline(always)]
+    /// Returns the number of past epochs whose visits survive a rebase.
+    pub fn history(&self) -> usize {
+        self.history.to_usize().unwrap()
+    }
+
+    #[inline(always)]
+    /// Returns the current epoch, starting from one and bumped by one at each clear.
+    pub fn epoch(&self) -> u64 {
+        self.offset + self.epoch.to_u64().unwrap()
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was already visited in the current epoch.
+    pub fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.visited[index.into_index()] == self.epoch
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited in the current epoch.
+    pub fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        self.visited[index.into_index()] = self.epoch;
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited in the current epoch and returns the previous value.
+    pub fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        core::mem::replace(&mut self.visited[index.into_index()], self.epoch) == self.epoch
+    }
+
+    #[inline(always)]
+    /// Returns the epoch in which the value at given index was last visited, if any.
+    ///
+    /// Visits older than the history may be forgotten once the values are rebased.
+    pub fn last_visit_epoch<U>(&self, index: U) -> Option<u64>
+    where
+        U: VisitedIndex,
+    {
+        let value = self.visited[index.into_index()];
+        if value.is_zero() {
+            None
+        } else {
+            Some(self.offset + value.to_u64().unwrap())
+        }
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was visited in any of the last `epochs` epochs, including the current one.
+    ///
+    /// The answer is exact as long as `epochs` is not larger than the history plus one,
+    /// as older visits may be forgotten once the values are rebased.
+    pub fn visited_within<U>(&self, index: U, epochs: usize) -> bool
+    where
+        U: VisitedIndex,
+    {
+        let value = self.visited[index.into_index()];
+        !value.is_zero()
+            && (self.epoch - value)
+                .to_usize()
+                .is_some_and(|age| age < epochs)
+    }
+
+    #[inline(always)]
+    /// Moves to the next epoch, so that no value is visited in it yet.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`](crate::prelude::Visited::clear): the epoch is
+    /// bumped by one, and when it reaches the maximal value the values are
+    /// rebased, so that the visits of the last `history` epochs are kept.
+    pub fn clear(&mut self) {
+        if bump_epoch(&mut self.epoch) {
+            self.rebase();
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Shifts the epochs down so that the previous one, i.e. the maximal value, becomes the history.
+    ///
+    /// # Implementative details
+    /// Values visited in the last `history` epochs are larger than the
+    /// shift, so they are still represented after subtracting it, while
+    /// all the older values saturate to zero, i.e. to never visited.
+    fn rebase(&mut self) {
+        let shift = T::max_value() - self.history;
+        self.visited.iter_mut().for_each(|v| {
+            *v = if *v > shift { *v - shift } else { T::zero() };
+        });
+        self.offset += shift.to_u64().unwrap();
+        self.epoch = self.history;
+        self.epoch += T::one();
+    }
+}
+
+impl<T> VisitedSet for RecencyVisited<T>
+where
+    T: Zero
+        + One
+        + Copy
+        + PartialOrd
+        + UpperBounded
+        + AddAssign
+        + Sub<Output = T>
+        + FromPrimitive
+        + ToPrimitive,
+{
+    #[inline(always)]
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        RecencyVisited::is_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        RecencyVisited::set_visited(self, index);
+    }
+
+    #[inline(always)]
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        RecencyVisited::set_and_get_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        RecencyVisited::clear(self);
+    }
+
+    #[inline(always)]
+    fn len(&self) -> Option<usize> {
+        Some(RecencyVisited::len(self))
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        RecencyVisited::len(self)
+    }
+}
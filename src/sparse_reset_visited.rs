@@ -0,0 +1,182 @@
+use core::ops::AddAssign;
+
+use alloc::{vec, vec::Vec};
+use num_traits::{bounds::UpperBounded, One, ToPrimitive, Zero};
+
+use crate::{epoch::bump_epoch, index::VisitedIndex, visited::Visited, visited_set::VisitedSet};
+
+/// Default number of values in each block tracked by [`SparseResetVisited`].
+pub const DEFAULT_BLOCK_SIZE: usize = 1024;
+
+#[derive(Clone, Debug)]
+/// Visited struct that, when the visited flag wraps around, only resets the blocks that were written.
+///
+/// The values are split into blocks of a power of two size, and a bitmap
+/// keeps track of the blocks that were written since the last reset. When
+/// the visited flag reaches the maximal value, only these blocks are zeroed,
+/// which is considerably cheaper than zeroing the whole vector when the
+/// iterations only ever visit a small portion of the values.
+pub struct SparseResetVisited<T> {
+    visited: Visited<T>,
+    dirty: Vec<u64>,
+    block_shift: u32,
+}
+
+impl<T> SparseResetVisited<T>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign + ToPrimitive,
+{
+    #[inline(always)]
+    /// Creates new zeroed sparse reset visited struct with given capacity.
+    pub fn zero(capacity: usize) -> Self {
+        Self::with_block_size(capacity, DEFAULT_BLOCK_SIZE)
+    }
+
+    #[inline(always)]
+    /// Creates new zeroed sparse reset visited struct with given capacity and block size.
+    ///
+    /// # Panics
+    /// If the block size is not a power of two.
+    pub fn with_block_size(capacity: usize, block_size: usize) -> Self {
+        assert!(
+            block_size.is_power_of_two(),
+            "the block size must be a power of two, but {} was provided",
+            block_size
+        );
+        let number_of_blocks = capacity.div_ceil(block_size);
+        Self {
+            visited: Visited::zero(capacity),
+            dirty: vec![0; number_of_blocks.div_ceil(64)],
+            block_shift: block_size.trailing_zeros(),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the number of values in the visited struct.
+    pub fn len(&self) -> usize {
+        self.visited.len()
+    }
+
+    #[inline(always)]
+    /// Returns whether the visited struct holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.visited.is_empty()
+    }
+
+    #[inline(always)]
+    /// Marks the block containing the provided index as written.
+    fn mark_dirty(&mut self, index: usize) {
+        let block = index >> self.block_shift;
+        self.dirty[block >> 6] |= 1 << (block & 63);
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was already visited.
+    pub fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.visited.is_visited(index)
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited.
+    pub fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        self.visited.set_visited(index);
+        self.mark_dirty(index);
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited and returns the previous value.
+    pub fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        let visited = self.visited.set_and_get_visited(index);
+        if !visited {
+            self.mark_dirty(index);
+        }
+        visited
+    }
+
+    #[inline(always)]
+    /// Clears all visited values.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`]: the only difference is that, when the
+    /// visited flag reaches the maximal value, only the blocks that were
+    /// written since the previous reset are zeroed.
+    pub fn clear(&mut self) {
+        if bump_epoch(&mut self.visited.visited_flag) {
+            self.reset();
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Zeroes the values of the written blocks.
+    fn reset(&mut self) {
+        let block_size = 1 << self.block_shift;
+        let len = self.visited.len();
+        for (word_index, word) in self.dirty.iter_mut().enumerate() {
+            while *word != 0 {
+                let block = word_index * 64 + word.trailing_zeros() as usize;
+                *word &= *word - 1;
+                let start = block * block_size;
+                let end = (start + block_size).min(len);
+                self.visited.visited[start..end].iter_mut().for_each(|v| {
+                    *v = T::zero();
+                });
+            }
+        }
+    }
+}
+
+impl<T> VisitedSet for SparseResetVisited<T>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign + ToPrimitive,
+{
+    #[inline(always)]
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        SparseResetVisited::is_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        SparseResetVisited::set_visited(self, index);
+    }
+
+    #[inline(always)]
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        SparseResetVisited::set_and_get_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        SparseResetVisited::clear(self);
+    }
+
+    #[inline(always)]
+    fn len(&self) -> Option<usize> {
+        Some(SparseResetVisited::len(self))
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        SparseResetVisited::len(self)
+    }
+}
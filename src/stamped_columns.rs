@@ -0,0 +1,181 @@
+use core::ops::AddAssign;
+
+use num_traits::{bounds::UpperBounded, One, ToPrimitive, Zero};
+
+use crate::{index::VisitedIndex, visited::Visited};
+
+/// Columnar storage of rows, to be guarded by the epoch stamps of a [`StampedColumns`].
+///
+/// This trait is meant to be derived on the struct describing a row with the
+/// `Stamped` derive macro, available with the `derive` feature, which generates
+/// a struct with a vector for each field of the row.
+pub trait Columns {
+    /// The type of the rows stored in the columns.
+    type Row;
+
+    /// Returns new columns holding the provided number of default rows.
+    fn with_len(len: usize) -> Self;
+
+    /// Returns the number of rows in the columns.
+    fn len(&self) -> usize;
+
+    /// Returns whether the columns hold no rows.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sets the row at given index to the default values.
+    fn reset_row(&mut self, index: usize);
+
+    /// Sets the row at given index to the provided values.
+    fn write_row(&mut self, index: usize, row: Self::Row);
+
+    /// Returns the row at given index.
+    fn read_row(&self, index: usize) -> Self::Row;
+}
+
+/// Access to the columns of a [`StampedColumns`], guarded by the current epoch.
+///
+/// The accessors generated by the `Stamped` derive macro are built on top of this trait.
+pub trait ColumnAccess {
+    /// The columns being guarded.
+    type Columns: Columns;
+
+    /// Returns a reference to the value at given index in the provided column, if its row was written in the current epoch.
+    fn column<U, V, F>(&self, index: U, column: F) -> Option<&V>
+    where
+        U: VisitedIndex,
+        F: FnOnce(&Self::Columns) -> &[V];
+
+    /// Returns a mutable reference to the value at given index in the provided column.
+    ///
+    /// If the row was not written in the current epoch, all of its
+    /// columns are first set to their default values.
+    fn column_mut<U, V, F>(&mut self, index: U, column: F) -> &mut V
+    where
+        U: VisitedIndex,
+        F: FnOnce(&mut Self::Columns) -> &mut [V];
+}
+
+#[derive(Clone, Debug)]
+/// Structure of arrays whose rows are only valid in the epoch they were written in.
+///
+/// A single column of epoch stamps guards all the columns, so that all the
+/// per-node state of an algorithm, such as the distances and the dependencies
+/// in the Brandes algorithm, is cleared together. Stale rows are reset to their
+/// default values when first written in a new epoch.
+pub struct StampedColumns<C, T> {
+    columns: C,
+    stamps: Visited<T>,
+}
+
+impl<C, T> StampedColumns<C, T>
+where
+    C: Columns,
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign + ToPrimitive,
+{
+    /// Creates new stamped columns with given capacity and no row written.
+    pub fn zero(capacity: usize) -> Self {
+        Self {
+            columns: C::with_len(capacity),
+            stamps: Visited::zero(capacity),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the number of rows in the stamped columns.
+    pub fn len(&self) -> usize {
+        self.stamps.len()
+    }
+
+    #[inline(always)]
+    /// Returns whether the stamped columns hold no rows.
+    pub fn is_empty(&self) -> bool {
+        self.stamps.is_empty()
+    }
+
+    #[inline(always)]
+    /// Returns a reference to the underlying columns, ignoring the epoch stamps.
+    pub fn columns(&self) -> &C {
+        &self.columns
+    }
+
+    #[inline(always)]
+    /// Returns whether the row at given index was written in the current epoch.
+    pub fn contains<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.stamps.is_visited(index)
+    }
+
+    #[inline(always)]
+    /// Returns the row at given index, if it was written in the current epoch.
+    pub fn get<U>(&self, index: U) -> Option<C::Row>
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        if self.stamps.is_visited(index) {
+            Some(self.columns.read_row(index))
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    /// Sets the row at given index.
+    pub fn insert<U>(&mut self, index: U, row: C::Row)
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        self.stamps.set_visited(index);
+        self.columns.write_row(index, row);
+    }
+
+    #[inline(always)]
+    /// Clears all rows.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`]: the values in the columns are left untouched,
+    /// and rows are reset lazily when first written in a new epoch.
+    pub fn clear(&mut self) {
+        self.stamps.clear();
+    }
+}
+
+impl<C, T> ColumnAccess for StampedColumns<C, T>
+where
+    C: Columns,
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign + ToPrimitive,
+{
+    type Columns = C;
+
+    #[inline(always)]
+    fn column<U, V, F>(&self, index: U, column: F) -> Option<&V>
+    where
+        U: VisitedIndex,
+        F: FnOnce(&C) -> &[V],
+    {
+        let index = index.into_index();
+        if self.stamps.is_visited(index) {
+            Some(&column(&self.columns)[index])
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn column_mut<U, V, F>(&mut self, index: U, column: F) -> &mut V
+    where
+        U: VisitedIndex,
+        F: FnOnce(&mut C) -> &mut [V],
+    {
+        let index = index.into_index();
+        if !self.stamps.set_and_get_visited(index) {
+            self.columns.reset_row(index);
+        }
+        &mut column(&mut self.columns)[index]
+    }
+}
@@ -0,0 +1,340 @@
+use core::{
+    fmt::{Debug, Formatter},
+    mem::MaybeUninit,
+    ops::AddAssign,
+};
+
+use alloc::{vec, vec::Vec};
+use num_traits::{bounds::UpperBounded, One, Zero};
+
+use crate::{epoch::bump_epoch, index::VisitedIndex};
+
+/// Vector of values that are only valid in the epoch they were written in.
+///
+/// Each value is stored alongside the epoch stamp of when it was last
+/// written, and values whose stamp differs from the current epoch are
+/// treated as missing, as for per-node state such as distances or parents
+/// in a BFS. Stale values are only dropped when overwritten, or when the
+/// epoch wraps around.
+pub struct StampedVec<V, T: Zero> {
+    // A value is initialized if and only if its stamp is not zero.
+    values: Vec<MaybeUninit<V>>,
+    stamps: Vec<T>,
+    pub(crate) epoch: T,
+}
+
+impl<V, T> StampedVec<V, T>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+{
+    /// Creates new empty stamped vector with given capacity.
+    pub fn zero(capacity: usize) -> Self {
+        Self {
+            values: (0..capacity).map(|_| MaybeUninit::uninit()).collect(),
+            stamps: vec![T::zero(); capacity],
+            epoch: T::one(),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the number of values in the stamped vector.
+    pub fn len(&self) -> usize {
+        self.stamps.len()
+    }
+
+    #[inline(always)]
+    /// Returns whether the stamped vector holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.stamps.is_empty()
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was written in the current epoch.
+    pub fn contains<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.stamps[index.into_index()] == self.epoch
+    }
+
+    #[inline(always)]
+    /// Returns a reference to the value at given index, if it was written in the current epoch.
+    pub fn get<U>(&self, index: U) -> Option<&V>
+    where
+        U: VisitedIndex,
+    {
+        self.get_at(index.into_index(), &self.epoch)
+    }
+
+    #[inline(always)]
+    /// Returns a reference to the value at given index, if it was written in the provided non-zero epoch.
+    pub(crate) fn get_at(&self, index: usize, epoch: &T) -> Option<&V> {
+        if self.stamps[index] == *epoch {
+            // SAFETY: the stamp is the provided epoch, which is never zero.
+            Some(unsafe { self.values[index].assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    /// Returns a mutable reference to the value at given index, if it was written in the current epoch.
+    pub fn get_mut<U>(&mut self, index: U) -> Option<&mut V>
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        if self.stamps[index] == self.epoch {
+            // SAFETY: the stamp is the current epoch, which is never zero.
+            Some(unsafe { self.values[index].assume_init_mut() })
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    /// Writes the value at given index, which must not have been written in the current epoch.
+    fn write(&mut self, index: usize, value: V) -> &mut V {
+        if !self.stamps[index].is_zero() {
+            // The stamp is zeroed first, so that if dropping the stale value
+            // panics, the value is not dropped again with the stamped vector.
+            self.stamps[index] = T::zero();
+            // SAFETY: the stamp was not zero, so the stale value is initialized.
+            unsafe { self.values[index].assume_init_drop() };
+        }
+        self.stamps[index] = self.epoch.clone();
+        self.values[index].write(value)
+    }
+
+    #[inline(always)]
+    /// Sets the value at given index, returning the previous one if it was written in the current epoch.
+    pub fn insert<U>(&mut self, index: U, value: V) -> Option<V>
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        if let Some(current) = self.get_mut(index) {
+            Some(core::mem::replace(current, value))
+        } else {
+            self.write(index, value);
+            None
+        }
+    }
+
+    #[inline(always)]
+    /// Returns a mutable reference to the value at given index, writing it with the provided function if missing.
+    pub fn get_or_insert_with<U, F>(&mut self, index: U, f: F) -> &mut V
+    where
+        U: VisitedIndex,
+        F: FnOnce() -> V,
+    {
+        self.entry(index).or_insert_with(f)
+    }
+
+    #[inline(always)]
+    /// Returns the entry at given index, for in-place manipulation.
+    pub fn entry<U>(&mut self, index: U) -> Entry<'_, V, T>
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        if self.stamps[index] == self.epoch {
+            Entry::Occupied(OccupiedEntry { vec: self, index })
+        } else {
+            Entry::Vacant(VacantEntry { vec: self, index })
+        }
+    }
+
+    /// Returns an iterator over the indices and the values written in the current epoch.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &V)> + '_ {
+        self.stamps
+            .iter()
+            .zip(self.values.iter())
+            .enumerate()
+            .filter(|(_, (stamp, _))| **stamp == self.epoch)
+            // SAFETY: the stamp is the current epoch, which is never zero.
+            .map(|(index, (_, value))| (index, unsafe { value.assume_init_ref() }))
+    }
+
+    #[inline(always)]
+    /// Clears all values.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`](crate::prelude::Visited::clear): the epoch is
+    /// bumped by one, and when it reaches the maximal value all the values
+    /// are dropped and the stamps are zeroed.
+    pub fn clear(&mut self) {
+        if bump_epoch(&mut self.epoch) {
+            self.reset();
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Drops all the values and zeroes all the stamps.
+    fn reset(&mut self) {
+        self.drop_values();
+    }
+}
+
+impl<V, T: Zero> StampedVec<V, T> {
+    /// Drops all the initialized values and zeroes their stamps.
+    pub(crate) fn drop_values(&mut self) {
+        for (stamp, value) in self.stamps.iter_mut().zip(self.values.iter_mut()) {
+            if !stamp.is_zero() {
+                *stamp = T::zero();
+                // SAFETY: the stamp was not zero, so the value is initialized.
+                unsafe { value.assume_init_drop() };
+            }
+        }
+    }
+}
+
+impl<V, T: Zero> Drop for StampedVec<V, T> {
+    fn drop(&mut self) {
+        if core::mem::needs_drop::<V>() {
+            self.drop_values();
+        }
+    }
+}
+
+impl<V, T> Debug for StampedVec<V, T>
+where
+    V: Debug,
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Entry of a [`StampedVec`], which may either be occupied or vacant in the current epoch.
+pub enum Entry<'a, V, T: Zero> {
+    /// The value at the index was written in the current epoch.
+    Occupied(OccupiedEntry<'a, V, T>),
+    /// The value at the index was not written in the current epoch.
+    Vacant(VacantEntry<'a, V, T>),
+}
+
+/// Entry of a [`StampedVec`] whose value was written in the current epoch.
+pub struct OccupiedEntry<'a, V, T: Zero> {
+    vec: &'a mut StampedVec<V, T>,
+    index: usize,
+}
+
+/// Entry of a [`StampedVec`] whose value was not written in the current epoch.
+pub struct VacantEntry<'a, V, T: Zero> {
+    vec: &'a mut StampedVec<V, T>,
+    index: usize,
+}
+
+impl<'a, V, T> Entry<'a, V, T>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    /// Returns the index of the entry.
+    pub fn index(&self) -> usize {
+        match self {
+            Entry::Occupied(entry) => entry.index(),
+            Entry::Vacant(entry) => entry.index(),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns a mutable reference to the value, writing the provided one if vacant.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns a mutable reference to the value, writing the one returned by the provided function if vacant.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns a mutable reference to the value, writing the default one if vacant.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    #[inline(always)]
+    /// Applies the provided function to the value if occupied.
+    pub fn and_modify<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, V, T> OccupiedEntry<'a, V, T>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    /// Returns the index of the entry.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[inline(always)]
+    /// Returns a reference to the value.
+    pub fn get(&self) -> &V {
+        // SAFETY: the entry is occupied, so the value is initialized.
+        unsafe { self.vec.values[self.index].assume_init_ref() }
+    }
+
+    #[inline(always)]
+    /// Returns a mutable reference to the value.
+    pub fn get_mut(&mut self) -> &mut V {
+        // SAFETY: the entry is occupied, so the value is initialized.
+        unsafe { self.vec.values[self.index].assume_init_mut() }
+    }
+
+    #[inline(always)]
+    /// Returns a mutable reference to the value, bound to the lifetime of the stamped vector.
+    pub fn into_mut(self) -> &'a mut V {
+        // SAFETY: the entry is occupied, so the value is initialized.
+        unsafe { self.vec.values[self.index].assume_init_mut() }
+    }
+
+    #[inline(always)]
+    /// Sets the value, returning the previous one.
+    pub fn insert(&mut self, value: V) -> V {
+        core::mem::replace(self.get_mut(), value)
+    }
+}
+
+impl<'a, V, T> VacantEntry<'a, V, T>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+{
+    #[inline(always)]
+    /// Returns the index of the entry.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[inline(always)]
+    /// Writes the value, returning a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.vec.write(self.index, value)
+    }
+}
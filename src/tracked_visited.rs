@@ -0,0 +1,142 @@
+use core::ops::AddAssign;
+
+use alloc::vec::{Drain, Vec};
+use num_traits::{bounds::UpperBounded, One, ToPrimitive, Zero};
+
+use crate::{index::VisitedIndex, visited::Visited, visited_set::VisitedSet};
+
+#[derive(Clone, Debug)]
+/// Visited struct that also keeps track of the indices visited in the current iteration.
+///
+/// Every index newly set as visited is recorded in a list, so that the
+/// visited indices can be enumerated in time proportional to their number
+/// rather than to the length of the visited struct.
+pub struct TrackedVisited<T> {
+    visited: Visited<T>,
+    touched: Vec<usize>,
+}
+
+impl<T> TrackedVisited<T>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign + ToPrimitive,
+{
+    #[inline(always)]
+    /// Creates new zeroed tracked visited struct with given capacity.
+    pub fn zero(capacity: usize) -> Self {
+        Self {
+            visited: Visited::zero(capacity),
+            touched: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the number of values visited in the current iteration.
+    ///
+    /// As for `HashSet::len`, this counts the visited values, while
+    /// [`VisitedSet::len`] returns the number of values that can be visited.
+    pub fn len(&self) -> usize {
+        self.touched.len()
+    }
+
+    #[inline(always)]
+    /// Returns whether no value was visited in the current iteration.
+    pub fn is_empty(&self) -> bool {
+        self.touched.is_empty()
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was already visited.
+    pub fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.visited.is_visited(index)
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited.
+    pub fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        self.set_and_get_visited(index);
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited and returns the previous value.
+    pub fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        let visited = self.visited.set_and_get_visited(index);
+        if !visited {
+            self.touched.push(index);
+        }
+        visited
+    }
+
+    #[inline(always)]
+    /// Returns an iterator over the indices visited in the current iteration, in the order they were visited.
+    pub fn iter_visited(&self) -> impl Iterator<Item = usize> + '_ {
+        self.touched.iter().copied()
+    }
+
+    #[inline(always)]
+    /// Clears all visited values and returns an iterator over the indices that were visited.
+    pub fn drain(&mut self) -> Drain<'_, usize> {
+        self.visited.clear();
+        self.touched.drain(..)
+    }
+
+    #[inline(always)]
+    /// Clears all visited values.
+    pub fn clear(&mut self) {
+        self.visited.clear();
+        self.touched.clear();
+    }
+}
+
+impl<T> VisitedSet for TrackedVisited<T>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign + ToPrimitive,
+{
+    #[inline(always)]
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        TrackedVisited::is_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        TrackedVisited::set_visited(self, index);
+    }
+
+    #[inline(always)]
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        TrackedVisited::set_and_get_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        TrackedVisited::clear(self);
+    }
+
+    #[inline(always)]
+    fn len(&self) -> Option<usize> {
+        Some(self.visited.len())
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.visited.len()
+    }
+}
@@ -0,0 +1,124 @@
+use core::ops::AddAssign;
+
+use alloc::{vec, vec::Vec};
+use num_traits::{bounds::UpperBounded, One, SaturatingAdd, ToPrimitive, Zero};
+
+use crate::{index::VisitedIndex, tracked_visited::TrackedVisited};
+
+#[derive(Clone, Debug)]
+/// Counter of the number of times each value was visited in the current iteration.
+///
+/// Each counter is guarded by an epoch stamp, and counters whose stamp
+/// differs from the current epoch are treated as zero and lazily reset when
+/// first incremented. The indices visited in the current iteration are also
+/// recorded, so that the most visited ones, such as the most frequent nodes
+/// of a random walk, are found without scanning all the counters.
+pub struct VisitCounter<T, C> {
+    visited: TrackedVisited<T>,
+    counts: Vec<C>,
+}
+
+impl<T, C> VisitCounter<T, C>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign + ToPrimitive,
+    C: Zero + One + Copy + SaturatingAdd,
+{
+    #[inline(always)]
+    /// Creates new zeroed visit counter with given capacity.
+    pub fn zero(capacity: usize) -> Self {
+        Self {
+            visited: TrackedVisited::zero(capacity),
+            counts: vec![C::zero(); capacity],
+        }
+    }
+
+    #[inline(always)]
+    /// Returns the number of counters in the visit counter.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    #[inline(always)]
+    /// Returns whether the visit counter holds no counters.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    #[inline(always)]
+    /// Returns the number of values visited at least once in the current iteration.
+    pub fn number_of_visited(&self) -> usize {
+        self.visited.len()
+    }
+
+    #[inline(always)]
+    /// Returns the number of times the value at given index was visited in the current iteration.
+    pub fn count<U>(&self, index: U) -> C
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        if self.visited.is_visited(index) {
+            self.counts[index]
+        } else {
+            C::zero()
+        }
+    }
+
+    #[inline(always)]
+    /// Increments the number of visits of the value at given index and returns the updated count.
+    ///
+    /// # Implementative details
+    /// The count saturates at the maximal value of `C` rather than
+    /// overflowing, so that a value visited more times than `C` can
+    /// represent is still reported among the most visited ones.
+    pub fn increment<U>(&mut self, index: U) -> C
+    where
+        U: VisitedIndex,
+    {
+        let index = index.into_index();
+        if !self.visited.set_and_get_visited(index) {
+            self.counts[index] = C::zero();
+        }
+        self.counts[index] = self.counts[index].saturating_add(&C::one());
+        self.counts[index]
+    }
+
+    #[inline(always)]
+    /// Returns an iterator over the indices visited in the current iteration and their counts, in the order they were first visited.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, C)> + '_ {
+        self.visited
+            .iter_visited()
+            .map(|index| (index, self.counts[index]))
+    }
+
+    /// Returns the `k` most visited indices of the current iteration and their counts, sorted by decreasing count.
+    ///
+    /// # Implementative details
+    /// Only the indices visited in the current iteration are considered, so
+    /// this takes time proportional to their number rather than to the length
+    /// of the visit counter. Ties are broken by increasing index.
+    pub fn top_k(&self, k: usize) -> Vec<(usize, C)>
+    where
+        C: Ord,
+    {
+        let mut counts: Vec<(usize, C)> = self.iter().collect();
+        let by_count = |a: &(usize, C), b: &(usize, C)| b.1.cmp(&a.1).then(a.0.cmp(&b.0));
+        if k < counts.len() {
+            counts.select_nth_unstable_by(k, by_count);
+            counts.truncate(k);
+        }
+        counts.sort_unstable_by(by_count);
+        counts
+    }
+
+    #[inline(always)]
+    /// Clears all counters.
+    ///
+    /// # Implementative details
+    /// See [`Visited::clear`](crate::prelude::Visited::clear): the counters
+    /// are left untouched, and are reset lazily when first incremented in a
+    /// new iteration.
+    pub fn clear(&mut self) {
+        self.visited.clear();
+    }
+}
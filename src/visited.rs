@@ -1,89 +1,506 @@
-use std::{intrinsics::unlikely, ops::AddAssign};
+use core::ops::{AddAssign, Bound, RangeBounds};
 
-use num_traits::{bounds::UpperBounded, AsPrimitive, One, Zero};
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+use num_traits::{bounds::UpperBounded, ConstOne, ConstZero, One, ToPrimitive, Zero};
 
+use crate::{epoch::bump_epoch, error::VisitedError, index::VisitedIndex, visited_set::VisitedSet};
+
+/// Number of values compared against the visited flag at once by the range scans.
+const SCAN_CHUNK_SIZE: usize = 64;
+
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+/// Visited struct backed by the storage `S`, which defaults to a `Vec<T>`.
+pub struct Visited<T, S = Vec<T>> {
+    pub(crate) visited: S,
+    pub(crate) visited_flag: T,
+    reset_strategy: ResetStrategy,
+    sweep_cursor: usize,
+    sweep_chunk: usize,
+}
+
+#[cfg(not(feature = "alloc"))]
 #[derive(Clone, Debug)]
-pub struct Visited<T> {
-    visited: Vec<T>,
-    visited_flag: T,
+/// Visited struct backed by the storage `S`.
+pub struct Visited<T, S> {
+    pub(crate) visited: S,
+    pub(crate) visited_flag: T,
+    reset_strategy: ResetStrategy,
+    sweep_cursor: usize,
+    sweep_chunk: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Strategy used by a visited struct to reset its values when the visited flag wraps around.
+pub enum ResetStrategy {
+    #[default]
+    /// All the values are zeroed at once when the visited flag reaches the maximal value.
+    ///
+    /// This has the lowest amortized cost, but the clear that triggers the
+    /// reset takes time proportional to the length of the visited struct.
+    Full,
+    /// Every clear zeroes a slice of the values, so that no full pass is ever needed.
+    ///
+    /// Each clear writes at most `len.div_ceil(T::max_value())` values, or up to
+    /// twice as many once the visited struct grew, making the cost of every
+    /// clear bounded rather than only amortized.
+    Incremental,
+}
+
+/// Visited struct backed by a borrowed buffer, which requires no allocator.
+pub type SliceVisited<'a, T> = Visited<T, &'a mut [T]>;
+
+/// Visited struct backed by an array of fixed capacity `N`, which requires no allocator.
+pub type ArrayVisited<T, const N: usize> = Visited<T, [T; N]>;
+
+/// Storage of the values of a visited struct.
+pub trait VisitedStorage<T>: AsRef<[T]> + AsMut<[T]> {
+    #[inline(always)]
+    /// Returns the number of values the storage can hold without reallocating.
+    fn capacity(&self) -> usize {
+        self.as_ref().len()
+    }
+}
+
+impl<T, const N: usize> VisitedStorage<T> for [T; N] {}
+
+impl<T> VisitedStorage<T> for &mut [T] {}
+
+#[cfg(feature = "alloc")]
+impl<T> VisitedStorage<T> for alloc::boxed::Box<[T]> {}
+
+#[cfg(feature = "alloc")]
+impl<T> VisitedStorage<T> for Vec<T> {
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        Vec::capacity(self)
+    }
+}
+
+impl<T, S> Visited<T, S> {
+    #[inline(always)]
+    /// Creates new visited struct from the provided storage, visited flag and reset strategy.
+    ///
+    /// The storage must only hold zeroes and values equal to the visited flag,
+    /// which satisfies the invariants of both reset strategies.
+    pub(crate) fn from_parts(storage: S, visited_flag: T, reset_strategy: ResetStrategy) -> Self {
+        Self {
+            visited: storage,
+            visited_flag,
+            reset_strategy,
+            sweep_cursor: 0,
+            sweep_chunk: 0,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    /// Consumes the visited struct and returns its storage, visited flag and reset strategy.
+    pub(crate) fn into_parts(self) -> (S, T, ResetStrategy) {
+        (self.visited, self.visited_flag, self.reset_strategy)
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<T> Visited<T>
 where
-    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign,
+    T: Zero + One + Clone,
 {
     #[inline(always)]
     /// Creates new zeroed visited struct with given capacity.
     pub fn zero(capacity: usize) -> Self {
+        Self::from_parts(vec![T::zero(); capacity], T::one(), ResetStrategy::Full)
+    }
+
+    #[inline(always)]
+    /// Returns the number of values the visited struct can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.visited.capacity()
+    }
+
+    #[inline(always)]
+    /// Reserves capacity for at least `additional` more values.
+    pub fn reserve(&mut self, additional: usize) {
+        self.visited.reserve(additional);
+    }
+
+    #[inline(always)]
+    /// Shortens the visited struct to the provided length, dropping the values past it.
+    pub fn truncate(&mut self, len: usize) {
+        self.visited.truncate(len);
+    }
+
+    #[inline(always)]
+    /// Shrinks the capacity of the visited struct as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.visited.shrink_to_fit();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Visited<T>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign + ToPrimitive,
+{
+    #[inline(always)]
+    /// Resizes the visited struct to the provided length.
+    ///
+    /// Values that are added are not visited, whatever the current visited flag.
+    ///
+    /// # Implementative details
+    /// With the [`ResetStrategy::Incremental`] strategy, growing past the
+    /// length covered by the current sweep restarts it, see [`Visited::push_unvisited`].
+    pub fn resize(&mut self, new_len: usize) {
+        self.grow_sweep(new_len);
+        self.visited.resize(new_len, T::zero());
+    }
+
+    #[inline(always)]
+    /// Appends a value that is not visited, whatever the current visited flag.
+    ///
+    /// # Implementative details
+    /// With the [`ResetStrategy::Incremental`] strategy, the values zeroed at
+    /// each clear are enough to sweep the whole vector before the flag wraps
+    /// around only up to a given length. Growing past it zeroes all the values
+    /// of previous iterations, taking linear time, and restarts the sweep with
+    /// at least twice as many values per clear, so that the cost of growing is
+    /// amortized while the cost of every clear stays bounded.
+    pub fn push_unvisited(&mut self) {
+        self.grow_sweep(self.len() + 1);
+        self.visited.push(T::zero());
+    }
+
+    #[inline(always)]
+    /// Restarts the incremental sweep if it cannot cover the provided length.
+    fn grow_sweep(&mut self, new_len: usize) {
+        if self.reset_strategy != ResetStrategy::Incremental {
+            return;
+        }
+        let sweep_chunk = self.sweep_chunk.max(Self::min_sweep_chunk(self.len()));
+        let min_sweep_chunk = Self::min_sweep_chunk(new_len);
+        if min_sweep_chunk > sweep_chunk {
+            self.restart_sweep(min_sweep_chunk.max(2 * sweep_chunk));
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Zeroes the values of previous iterations and restarts the sweep with the provided chunk size.
+    fn restart_sweep(&mut self, sweep_chunk: usize) {
+        let visited_flag = self.visited_flag.clone();
+        self.visited.iter_mut().for_each(|v| {
+            if *v != visited_flag {
+                *v = T::zero();
+            }
+        });
+        self.sweep_cursor = 0;
+        self.sweep_chunk = sweep_chunk;
+    }
+}
+
+impl<T, const N: usize> Visited<T, [T; N]>
+where
+    T: ConstZero + ConstOne + Copy,
+{
+    #[inline(always)]
+    /// Creates new zeroed visited struct with capacity `N`.
+    ///
+    /// This method is `const`, so that an empty visited struct may be defined as a constant.
+    pub const fn new() -> Self {
         Self {
-            visited: vec![T::zero(); capacity],
-            visited_flag: T::one(),
+            visited: [T::ZERO; N],
+            visited_flag: T::ONE,
+            reset_strategy: ResetStrategy::Full,
+            sweep_cursor: 0,
+            sweep_chunk: 0,
         }
     }
+}
+
+impl<T, const N: usize> Default for Visited<T, [T; N]>
+where
+    T: ConstZero + ConstOne + Copy,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S> Visited<T, S>
+where
+    T: Zero + One + Clone,
+    S: AsMut<[T]>,
+{
+    #[inline(always)]
+    /// Creates new visited struct using the provided storage, which is zeroed.
+    ///
+    /// The length of the visited struct is the length of the storage.
+    pub fn with_storage(mut storage: S) -> Self {
+        storage.as_mut().iter_mut().for_each(|v| {
+            *v = T::zero();
+        });
+        Self::from_parts(storage, T::one(), ResetStrategy::Full)
+    }
+}
+
+impl<T, S> Visited<T, S>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign + ToPrimitive,
+    S: AsRef<[T]> + AsMut<[T]>,
+{
+    #[inline(always)]
+    /// Returns the number of values in the visited struct.
+    pub fn len(&self) -> usize {
+        self.visited.as_ref().len()
+    }
+
+    #[inline(always)]
+    /// Returns whether the visited struct holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.visited.as_ref().is_empty()
+    }
 
     #[inline(always)]
     /// Returns whether the value at given index was already visited.
     pub fn is_visited<U>(&self, index: U) -> bool
     where
-        U: AsPrimitive<usize>,
+        U: VisitedIndex,
     {
-        self.visited[index.as_()] == self.visited_flag
+        self.visited.as_ref()[index.into_index()] == self.visited_flag
     }
 
     #[inline(always)]
     /// Sets the value at provided index as visited.
     pub fn set_visited<U>(&mut self, index: U)
     where
-        U: AsPrimitive<usize>,
+        U: VisitedIndex,
     {
-        self.visited[index.as_()] = self.visited_flag.clone();
+        self.visited.as_mut()[index.into_index()] = self.visited_flag.clone();
     }
 
     #[inline(always)]
     /// Sets the value at provided index as visited and returns the previous value.
     pub fn set_and_get_visited<U>(&mut self, index: U) -> bool
     where
-        U: AsPrimitive<usize>,
+        U: VisitedIndex,
+    {
+        core::mem::replace(
+            &mut self.visited.as_mut()[index.into_index()],
+            self.visited_flag.clone(),
+        ) == self.visited_flag
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as not visited.
+    ///
+    /// # Implementative details
+    /// The visited flag starts from one and, when it reaches the maximal
+    /// value, it is reset back to one, so it is never zero. Writing zero
+    /// therefore marks the value as not visited in the current iteration
+    /// and in all the following ones, also across the reset.
+    pub fn set_unvisited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        self.visited.as_mut()[index.into_index()] = T::zero();
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as not visited and returns the previous value.
+    pub fn set_and_get_unvisited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
     {
-        std::mem::replace(&mut self.visited[index.as_()], self.visited_flag.clone())
+        core::mem::replace(&mut self.visited.as_mut()[index.into_index()], T::zero())
             == self.visited_flag
     }
 
     #[inline(always)]
-    /// Sets data-racingly the value at provided index as visited.
+    /// Returns whether the value at given index was already visited, without bounds checks.
     ///
     /// # Safety
-    /// This method allows mutable access to the data-structure
-    /// that may be allowed in parallel by multiple threads.
-    /// Use this method carefully, being aware that there might
-    /// be collisions from the different threads emploing this
-    /// method.
-    pub unsafe fn set_visited_racing<U>(&self, index: U)
+    /// The index must be smaller than the length of the visited struct,
+    /// as otherwise this method reads out of the bounds of the storage.
+    pub unsafe fn is_visited_unchecked<U>(&self, index: U) -> bool
     where
-        U: AsPrimitive<usize>,
+        U: VisitedIndex,
     {
-        (&mut *(self.visited.as_ptr() as *mut Vec<T>))[index.as_()] = self.visited_flag.clone();
+        *self.visited.as_ref().get_unchecked(index.into_index()) == self.visited_flag
     }
 
     #[inline(always)]
-    /// Sets data-racingly the value at provided index as visited  and returns the previous value.
+    /// Sets the value at provided index as visited, without bounds checks.
     ///
     /// # Safety
-    /// This method allows mutable access to the data-structure
-    /// that may be allowed in parallel by multiple threads.
-    /// Use this method carefully, being aware that there might
-    /// be collisions from the different threads emploing this
-    /// method.
-    pub unsafe fn set_and_get_visited_racing<U>(&self, index: U) -> bool
+    /// The index must be smaller than the length of the visited struct,
+    /// as otherwise this method writes out of the bounds of the storage.
+    pub unsafe fn set_visited_unchecked<U>(&mut self, index: U)
     where
-        U: AsPrimitive<usize>,
+        U: VisitedIndex,
     {
-        std::mem::replace(
-            &mut (&mut *(self.visited.as_ptr() as *mut Vec<T>))[index.as_()],
+        *self.visited.as_mut().get_unchecked_mut(index.into_index()) = self.visited_flag.clone();
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited and returns the previous value, without bounds checks.
+    ///
+    /// # Safety
+    /// The index must be smaller than the length of the visited struct,
+    /// as otherwise this method writes out of the bounds of the storage.
+    pub unsafe fn set_and_get_visited_unchecked<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        core::mem::replace(
+            self.visited.as_mut().get_unchecked_mut(index.into_index()),
             self.visited_flag.clone(),
         ) == self.visited_flag
     }
 
+    #[inline(always)]
+    /// Returns the provided index as an `usize` if it is within the length of the visited struct.
+    fn checked_index<U>(&self, index: U) -> Result<usize, VisitedError>
+    where
+        U: VisitedIndex,
+    {
+        let index = index
+            .try_into_index()
+            .ok_or(VisitedError::LossyIndexConversion)?;
+        let len = self.len();
+        if index < len {
+            Ok(index)
+        } else {
+            Err(VisitedError::IndexOutOfBounds { index, len })
+        }
+    }
+
+    #[inline(always)]
+    /// Returns whether the value at given index was already visited, or an error if the index is invalid.
+    pub fn try_is_visited<U>(&self, index: U) -> Result<bool, VisitedError>
+    where
+        U: VisitedIndex,
+    {
+        let index = self.checked_index(index)?;
+        Ok(self.is_visited(index))
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited, or returns an error if the index is invalid.
+    pub fn try_set_visited<U>(&mut self, index: U) -> Result<(), VisitedError>
+    where
+        U: VisitedIndex,
+    {
+        let index = self.checked_index(index)?;
+        self.set_visited(index);
+        Ok(())
+    }
+
+    #[inline(always)]
+    /// Sets the value at provided index as visited and returns the previous value, or an error if the index is invalid.
+    pub fn try_set_and_get_visited<U>(&mut self, index: U) -> Result<bool, VisitedError>
+    where
+        U: VisitedIndex,
+    {
+        let index = self.checked_index(index)?;
+        Ok(self.set_and_get_visited(index))
+    }
+
+    #[inline(always)]
+    /// Sets all the values in the provided range as visited.
+    ///
+    /// # Panics
+    /// If the range is out of bounds.
+    pub fn set_range_visited<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.visited.as_mut()[bounds(&range)].fill(self.visited_flag.clone());
+    }
+
+    #[inline(always)]
+    /// Returns whether all the values in the provided range were already visited.
+    ///
+    /// # Panics
+    /// If the range is out of bounds.
+    pub fn all_visited<R>(&self, range: R) -> bool
+    where
+        R: RangeBounds<usize>,
+    {
+        let values = &self.visited.as_ref()[bounds(&range)];
+        position(values, &self.visited_flag, false).is_none()
+    }
+
+    #[inline(always)]
+    /// Returns whether any of the values in the provided range was already visited.
+    ///
+    /// # Panics
+    /// If the range is out of bounds.
+    pub fn any_visited<R>(&self, range: R) -> bool
+    where
+        R: RangeBounds<usize>,
+    {
+        let values = &self.visited.as_ref()[bounds(&range)];
+        position(values, &self.visited_flag, true).is_some()
+    }
+
+    #[inline(always)]
+    /// Returns the number of values in the provided range that were already visited.
+    ///
+    /// # Panics
+    /// If the range is out of bounds.
+    pub fn count_visited<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<usize>,
+    {
+        let values = &self.visited.as_ref()[bounds(&range)];
+        values
+            .iter()
+            .filter(|value| **value == self.visited_flag)
+            .count()
+    }
+
+    #[inline(always)]
+    /// Returns the smallest index not smaller than the provided one whose value was not visited yet.
+    ///
+    /// This is handy to find the seed of the next connected component once a traversal is done.
+    pub fn first_unvisited<U>(&self, from: U) -> Option<usize>
+    where
+        U: VisitedIndex,
+    {
+        let from = from.into_index();
+        let values = self.visited.as_ref().get(from..)?;
+        position(values, &self.visited_flag, false).map(|index| from + index)
+    }
+
+    #[inline(always)]
+    /// Returns the smallest index not smaller than the provided one whose value was already visited.
+    pub fn next_visited<U>(&self, from: U) -> Option<usize>
+    where
+        U: VisitedIndex,
+    {
+        let from = from.into_index();
+        let values = self.visited.as_ref().get(from..)?;
+        position(values, &self.visited_flag, true).map(|index| from + index)
+    }
+
+    #[inline(always)]
+    /// Returns the strategy used to reset the values when the visited flag wraps around.
+    pub fn reset_strategy(&self) -> ResetStrategy {
+        self.reset_strategy
+    }
+
+    /// Sets the strategy used to reset the values when the visited flag wraps around.
+    ///
+    /// # Implementative details
+    /// The two strategies rely on different invariants on the values,
+    /// so all the values are zeroed and the visited struct is cleared.
+    pub fn set_reset_strategy(&mut self, reset_strategy: ResetStrategy) {
+        self.reset_strategy = reset_strategy;
+        self.reset();
+    }
+
     #[inline(always)]
     /// Clears all visited values.
     ///
@@ -96,14 +513,142 @@ where
     /// cases we may have not visited some elments in none of the
     /// iterations and we may erroneously set these elements as
     /// `visited` while they where never visited.
+    ///
+    /// With the [`ResetStrategy::Incremental`] strategy, a slice of the
+    /// values is instead zeroed at every clear, so that by the time the
+    /// flag wraps around all the values were already zeroed since they
+    /// were last written.
     pub fn clear(&mut self) {
-        if unlikely(self.visited_flag == T::max_value()) {
-            self.visited_flag = T::one();
-            self.visited.iter_mut().for_each(|v| {
-                *v = T::zero();
-            });
-        } else {
-            self.visited_flag += T::one();
+        if self.reset_strategy == ResetStrategy::Incremental {
+            self.clear_incremental();
+        } else if bump_epoch(&mut self.visited_flag) {
+            self.reset();
         }
     }
+
+    #[inline(always)]
+    /// Clears all visited values, zeroing the next slice of values.
+    ///
+    /// # Implementative details
+    /// A value written while the visited flag is `f` is erroneously
+    /// considered visited only once the flag, wrapping around, is `f`
+    /// again, which happens `T::max_value()` clears later. By zeroing
+    /// at least `len.div_ceil(T::max_value())` values at each clear, cycling
+    /// over the vector, every value is zeroed at least once within that many
+    /// clears, so the flag may simply wrap back to one with no full pass.
+    /// Zeroing values while clearing is always correct, as no value is
+    /// visited in the new iteration yet. As this only holds while the length
+    /// does not outgrow the sweep, growing methods may restart it.
+    fn clear_incremental(&mut self) {
+        // The values that may be mistaken for visited ones are zeroed by the sweep.
+        bump_epoch(&mut self.visited_flag);
+        let chunk_size = self.sweep_chunk.max(Self::min_sweep_chunk(self.len()));
+        let values = self.visited.as_mut();
+        if values.is_empty() {
+            return;
+        }
+        if self.sweep_cursor >= values.len() {
+            self.sweep_cursor = 0;
+        }
+        let end = (self.sweep_cursor + chunk_size).min(values.len());
+        values[self.sweep_cursor..end].iter_mut().for_each(|v| {
+            *v = T::zero();
+        });
+        self.sweep_cursor = if end == values.len() { 0 } else { end };
+    }
+
+    #[inline(always)]
+    /// Returns the number of values to zero at each clear to sweep the provided length before the flag wraps around.
+    fn min_sweep_chunk(len: usize) -> usize {
+        len.div_ceil(T::max_value().to_usize().unwrap_or(usize::MAX))
+    }
+
+    #[cold]
+    #[inline(never)]
+    /// Resets the visited flag and zeroes all the values.
+    fn reset(&mut self) {
+        self.visited_flag = T::one();
+        self.sweep_cursor = 0;
+        self.visited.as_mut().iter_mut().for_each(|v| {
+            *v = T::zero();
+        });
+    }
+}
+
+#[inline(always)]
+/// Returns the provided range as a pair of bounds, which can be used to index a slice.
+fn bounds<R: RangeBounds<usize>>(range: &R) -> (Bound<usize>, Bound<usize>) {
+    (range.start_bound().cloned(), range.end_bound().cloned())
+}
+
+#[inline(always)]
+/// Returns the position of the first value that is visited, if `visited` is true, or unvisited otherwise.
+///
+/// # Implementative details
+/// The values are compared against the flag in chunks of [`SCAN_CHUNK_SIZE`]
+/// with no early exit within a chunk, so that the comparisons can be
+/// vectorized, and only the chunk containing the match is scanned again.
+fn position<T: PartialEq>(values: &[T], visited_flag: &T, visited: bool) -> Option<usize> {
+    let is_match = |value: &T| (value == visited_flag) == visited;
+    let mut chunks = values.chunks_exact(SCAN_CHUNK_SIZE);
+    let mut offset = 0;
+    for chunk in &mut chunks {
+        if chunk
+            .iter()
+            .fold(false, |found, value| found | is_match(value))
+        {
+            return chunk.iter().position(is_match).map(|index| offset + index);
+        }
+        offset += SCAN_CHUNK_SIZE;
+    }
+    chunks
+        .remainder()
+        .iter()
+        .position(is_match)
+        .map(|index| offset + index)
+}
+
+impl<T, S> VisitedSet for Visited<T, S>
+where
+    T: Zero + One + Clone + PartialOrd + UpperBounded + AddAssign + ToPrimitive,
+    S: VisitedStorage<T>,
+{
+    #[inline(always)]
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        Visited::is_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        Visited::set_visited(self, index);
+    }
+
+    #[inline(always)]
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        Visited::set_and_get_visited(self, index)
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        Visited::clear(self);
+    }
+
+    #[inline(always)]
+    fn len(&self) -> Option<usize> {
+        Some(Visited::len(self))
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.visited.capacity()
+    }
 }
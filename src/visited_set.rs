@@ -0,0 +1,137 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::BuildHasher;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+use crate::index::VisitedIndex;
+
+/// Common interface of the visited structs, allowing algorithms to be generic over the marking strategy.
+///
+/// Besides the visited structs of this crate, the trait is also implemented
+/// for `Vec<bool>` and, with the `std` feature, for `HashSet<usize>`, which
+/// may be used as reference implementations.
+pub trait VisitedSet {
+    /// Returns whether the value at given index was already visited.
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex;
+
+    /// Sets the value at provided index as visited.
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex;
+
+    /// Sets the value at provided index as visited and returns the previous value.
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex;
+
+    /// Clears all visited values.
+    fn clear(&mut self);
+
+    /// Returns the number of indices that can be set as visited, i.e. one past the largest valid index.
+    ///
+    /// Sparse visited sets such as `HashSet<usize>`, which accept any index,
+    /// return `None`. Note that this is not the number of indices set as
+    /// visited, which is instead what the inherent `len` of the visited sets
+    /// tracking them, such as `HashSet<usize>` or `TrackedVisited`, returns.
+    fn len(&self) -> Option<usize>;
+
+    /// Returns whether no index can be set as visited.
+    fn is_empty(&self) -> bool {
+        self.len() == Some(0)
+    }
+
+    /// Returns the number of indices the visited set can hold without reallocating.
+    ///
+    /// Dense visited sets hold every index smaller than their length, so this
+    /// is the length they may grow to without reallocating, while sparse ones
+    /// only hold the indices that were set as visited.
+    fn capacity(&self) -> usize;
+}
+
+#[cfg(feature = "alloc")]
+impl VisitedSet for Vec<bool> {
+    #[inline(always)]
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self[index.into_index()]
+    }
+
+    #[inline(always)]
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        self[index.into_index()] = true;
+    }
+
+    #[inline(always)]
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        core::mem::replace(&mut self[index.into_index()], true)
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        self.fill(false);
+    }
+
+    #[inline(always)]
+    fn len(&self) -> Option<usize> {
+        Some(Vec::len(self))
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        Vec::capacity(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: BuildHasher> VisitedSet for HashSet<usize, S> {
+    #[inline(always)]
+    fn is_visited<U>(&self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        self.contains(&index.into_index())
+    }
+
+    #[inline(always)]
+    fn set_visited<U>(&mut self, index: U)
+    where
+        U: VisitedIndex,
+    {
+        self.insert(index.into_index());
+    }
+
+    #[inline(always)]
+    fn set_and_get_visited<U>(&mut self, index: U) -> bool
+    where
+        U: VisitedIndex,
+    {
+        !self.insert(index.into_index())
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        HashSet::clear(self);
+    }
+
+    #[inline(always)]
+    fn len(&self) -> Option<usize> {
+        None
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        HashSet::capacity(self)
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::count_claims;
+use visited_rs::prelude::*;
+
+const NUMBER_OF_THREADS: usize = 8;
+const NUMBER_OF_VALUES: usize = 512;
+
+#[test]
+fn test_try_claim_is_won_exactly_once_per_epoch() {
+    let mut visited = AtomicVisited::<u8>::zero(NUMBER_OF_VALUES);
+    // More than twice the number of flags, so that the flag wraps around.
+    for epoch in 0..600 {
+        let claims = count_claims(NUMBER_OF_THREADS, NUMBER_OF_VALUES, epoch, |index| {
+            visited.try_claim(index)
+        });
+        assert!(claims.iter().all(|claims| *claims == 1), "epoch {epoch}");
+        assert!((0..NUMBER_OF_VALUES).all(|index| visited.is_visited(index)));
+        visited.clear();
+        assert!((0..NUMBER_OF_VALUES).all(|index| !visited.is_visited(index)));
+    }
+}
+
+#[test]
+fn test_concurrent_set_and_get_is_won_exactly_once_per_epoch() {
+    let mut visited = ConcurrentVisited::<u8>::zero(NUMBER_OF_VALUES);
+    for epoch in 0..600 {
+        let claims = count_claims(NUMBER_OF_THREADS, NUMBER_OF_VALUES, epoch, |index| {
+            !visited.set_and_get_visited(index)
+        });
+        assert!(claims.iter().all(|claims| *claims == 1), "epoch {epoch}");
+        visited.clear();
+    }
+}
+
+#[test]
+fn test_round_trip_from_incremental_visited() {
+    let mut visited = Visited::<u8>::zero(NUMBER_OF_VALUES);
+    visited.set_reset_strategy(ResetStrategy::Incremental);
+    let mut expected = vec![false; NUMBER_OF_VALUES];
+    for round in 0..1000 {
+        // Values written before the flag wrapped around may exceed it, and
+        // must not be mistaken for visited ones after the conversions.
+        let index = (round * 37) % NUMBER_OF_VALUES;
+        visited.set_visited(index);
+        expected[index] = true;
+        if round % 97 == 0 {
+            let concurrent: ConcurrentVisited<u8> = visited.into();
+            concurrent.set_visited((index + 1) % NUMBER_OF_VALUES);
+            expected[(index + 1) % NUMBER_OF_VALUES] = true;
+            let atomic: AtomicVisited<u8> = Visited::from(concurrent).into();
+            let claimed = (index + 2) % NUMBER_OF_VALUES;
+            assert_eq!(atomic.try_claim(claimed), !expected[claimed]);
+            expected[claimed] = true;
+            visited = atomic.into();
+            assert_eq!(visited.reset_strategy(), ResetStrategy::Incremental);
+        }
+        for (index, expected) in expected.iter().enumerate() {
+            assert_eq!(visited.is_visited(index), *expected, "round {round}");
+        }
+        visited.clear();
+        expected.fill(false);
+    }
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "alloc")]
+
+use visited_rs::prelude::*;
+
+#[test]
+fn test_colors_across_wrap_around() {
+    let len = 9;
+    let mut colors = ColorMarks::<u8>::zero(len);
+    // With `u8`, the base wraps around every 127 rounds.
+    for round in 0..400 {
+        for index in 0..len {
+            assert_eq!(colors.color(index), Color::White, "round {round}");
+        }
+        let gray = round % len;
+        let black = (round + 4) % len;
+        colors.set_gray(gray);
+        colors.set_black(black);
+        assert_eq!(colors.color(gray), Color::Gray, "round {round}");
+        assert_eq!(colors.color(black), Color::Black, "round {round}");
+        // A gray value turns black, as when a DFS leaves a node.
+        colors.set_black(gray);
+        assert_eq!(colors.color(gray), Color::Black, "round {round}");
+        colors.set_gray((round + 2) % len);
+        assert_eq!(
+            colors.color((round + 2) % len),
+            Color::Gray,
+            "round {round}"
+        );
+        assert_eq!(
+            colors.color((round + 1) % len),
+            Color::White,
+            "round {round}"
+        );
+        colors.clear();
+    }
+}
@@ -0,0 +1,58 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use std::sync::Barrier;
+use std::thread;
+
+/// Minimal linear congruential generator, to avoid depending on a random crate.
+pub struct Lcg(pub u64);
+
+impl Lcg {
+    /// Returns a pseudo-random value smaller than the provided modulo.
+    pub fn next(&mut self, modulo: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) as usize % modulo
+    }
+}
+
+/// Has several threads race to claim every index, returning how many times each index was claimed.
+///
+/// Each thread visits all the indices in a different order, starting from
+/// an offset depending on the thread and on the provided epoch, and all of
+/// them are released at once by a barrier to maximize the contention.
+pub fn count_claims<F>(
+    number_of_threads: usize,
+    number_of_values: usize,
+    epoch: usize,
+    claim: F,
+) -> Vec<usize>
+where
+    F: Fn(usize) -> bool + Sync,
+{
+    let barrier = Barrier::new(number_of_threads);
+    let mut claims = vec![0_usize; number_of_values];
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..number_of_threads)
+            .map(|thread_id| {
+                let claim = &claim;
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    barrier.wait();
+                    (0..number_of_values)
+                        .map(|index| (index + thread_id * 61 + epoch) % number_of_values)
+                        .filter(|index| claim(*index))
+                        .collect::<Vec<usize>>()
+                })
+            })
+            .collect();
+        for handle in handles {
+            for index in handle.join().unwrap() {
+                claims[index] += 1;
+            }
+        }
+    });
+    claims
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "alloc")]
+
+use core::hash::{BuildHasher, Hasher};
+use std::collections::{hash_map::RandomState, HashSet};
+
+mod common;
+
+use common::Lcg;
+use visited_rs::prelude::*;
+
+/// Hasher returning the key itself, so that tests control which keys collide.
+#[derive(Default)]
+struct IdentityHasher(u64);
+
+impl Hasher for IdentityHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = self.0 << 8 | u64::from(*byte);
+        }
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.0 = value as u64;
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct BuildIdentityHasher;
+
+impl BuildHasher for BuildIdentityHasher {
+    type Hasher = IdentityHasher;
+
+    fn build_hasher(&self) -> IdentityHasher {
+        IdentityHasher::default()
+    }
+}
+
+#[test]
+fn test_probing_skips_stale_slots() {
+    let mut visited = HashVisited::<usize, u8, _>::with_capacity_and_hasher(6, BuildIdentityHasher);
+    let capacity = visited.capacity();
+    // All these keys share the first slot, and end up next to each other.
+    visited.set_visited(0);
+    visited.set_visited(8);
+    visited.set_visited(16);
+    visited.clear();
+    // The key now lands in the first stale slot of the sequence.
+    assert!(!visited.set_and_get_visited(16));
+    assert!(visited.is_visited(&16));
+    assert!(!visited.is_visited(&0));
+    assert!(!visited.is_visited(&8));
+    assert!(!visited.set_and_get_visited(8));
+    assert!(visited.set_and_get_visited(16));
+    assert!(visited.set_and_get_visited(8));
+    assert!(!visited.is_visited(&0));
+    assert_eq!(visited.len(), 2);
+    assert_eq!(visited.capacity(), capacity);
+}
+
+#[test]
+fn test_revisiting_at_the_load_factor_does_not_grow() {
+    let mut visited = HashVisited::<usize, u8, _>::with_hasher(BuildIdentityHasher);
+    visited.set_visited(0);
+    let capacity = visited.capacity();
+    for key in 1..capacity {
+        visited.set_visited(key);
+    }
+    assert_eq!(visited.len(), capacity);
+    for key in 0..capacity {
+        assert!(visited.set_and_get_visited(key));
+    }
+    assert_eq!(visited.capacity(), capacity);
+    assert!(!visited.set_and_get_visited(capacity));
+    assert!(visited.capacity() > capacity);
+}
+
+#[test]
+fn test_grow_within_an_epoch() {
+    let mut visited = HashVisited::<usize, u8, _>::with_hasher(BuildIdentityHasher);
+    for key in 0..20 {
+        visited.set_visited(key * 8);
+    }
+    visited.clear();
+    // Leave stale keys around, then grow several times in the same epoch.
+    for key in 0..100 {
+        assert!(!visited.set_and_get_visited(key * 3));
+    }
+    for key in 0..100 {
+        assert!(visited.is_visited(&(key * 3)));
+    }
+    for key in 0..20 {
+        assert_eq!(visited.is_visited(&(key * 8)), (key * 8) % 3 == 0);
+    }
+    assert_eq!(visited.len(), 100);
+}
+
+#[test]
+fn test_wrap_around_reset() {
+    let mut visited = HashVisited::<usize, u8, _>::with_hasher(BuildIdentityHasher);
+    for epoch in 0..600 {
+        for key in 0..10 {
+            assert!(!visited.is_visited(&(epoch + key + 1)));
+        }
+        for key in 0..10 {
+            assert!(!visited.set_and_get_visited(epoch + key));
+        }
+        assert!(visited.is_visited(&epoch));
+        assert_eq!(visited.len(), 10);
+        visited.clear();
+        assert!(!visited.is_visited(&epoch));
+    }
+}
+
+#[test]
+fn test_hash_visited_matches_hash_set() {
+    let mut random = Lcg(7);
+    let mut visited = HashVisited::<usize, u8, _>::with_hasher(RandomState::new());
+    let mut expected = HashSet::new();
+    for _ in 0..600 {
+        let number_of_keys = random.next(50);
+        for _ in 0..number_of_keys {
+            let key = random.next(200);
+            assert_eq!(visited.is_visited(&key), expected.contains(&key));
+            assert_eq!(visited.set_and_get_visited(key), !expected.insert(key));
+        }
+        assert_eq!(visited.len(), expected.len());
+        visited.clear();
+        expected.clear();
+    }
+}
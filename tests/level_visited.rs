@@ -0,0 +1,75 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::Lcg;
+use visited_rs::prelude::*;
+
+#[test]
+fn test_levels_match_reference_across_rounds() {
+    let len = 50;
+    let mut random = Lcg(13);
+    let mut levels = LevelVisited::<u8>::zero(len);
+    let mut expected: Vec<Option<usize>> = vec![None; len];
+    for round in 0..2000 {
+        // Rounds of varying depth, some of which exceed the remaining headroom.
+        let depth = random.next(80);
+        for _ in 0..random.next(20) {
+            let index = random.next(len);
+            let level = random.next(depth + 1);
+            if random.next(2) == 0 {
+                levels.set_level(index, level);
+            } else {
+                assert_eq!(levels.set_and_get_level(index, level), expected[index]);
+            }
+            expected[index] = Some(level);
+        }
+        for (index, expected) in expected.iter().enumerate() {
+            assert_eq!(levels.level(index), *expected, "round {round}");
+            assert_eq!(levels.is_visited(index), expected.is_some());
+        }
+        levels.clear();
+        expected.fill(None);
+        for index in 0..len {
+            assert_eq!(levels.level(index), None, "round {round}");
+        }
+    }
+}
+
+#[test]
+fn test_rebase_keeps_the_levels_of_the_current_iteration() {
+    let mut levels = LevelVisited::<u8>::zero(10);
+    // Move the base up to 203, leaving a headroom of 52 levels.
+    levels.set_level(9, 100);
+    levels.clear();
+    levels.set_level(8, 100);
+    levels.clear();
+    levels.set_level(0, 0);
+    levels.set_level(1, 30);
+    levels.set_level(2, 52);
+    // This level does not fit in the headroom, so the values are rebased.
+    levels.set_level(3, 60);
+    assert_eq!(levels.level(0), Some(0));
+    assert_eq!(levels.level(1), Some(30));
+    assert_eq!(levels.level(2), Some(52));
+    assert_eq!(levels.level(3), Some(60));
+    assert_eq!(levels.level(8), None);
+    assert_eq!(levels.level(9), None);
+    assert_eq!(levels.set_and_get_level(1, 254), Some(30));
+    levels.clear();
+    for index in 0..10 {
+        assert_eq!(levels.level(index), None);
+    }
+    levels.set_level(4, 1);
+    assert_eq!(levels.level(4), Some(1));
+    assert_eq!(levels.level(1), None);
+}
+
+#[test]
+#[should_panic(expected = "level 255 cannot be represented")]
+fn test_level_larger_than_the_flag_panics() {
+    let mut levels = LevelVisited::<u8>::zero(10);
+    levels.set_level(0, 254);
+    assert_eq!(levels.level(0), Some(254));
+    levels.set_level(1, 255);
+}
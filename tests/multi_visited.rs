@@ -0,0 +1,51 @@
+#![cfg(feature = "alloc")]
+
+use visited_rs::prelude::*;
+
+#[test]
+fn test_lanes_are_independent() {
+    let mut visited = MultiVisited::<u8, 3>::zero(10);
+    assert_eq!(visited.lanes(), 3);
+    assert_eq!(visited.len(), 10);
+    visited.set_visited(0, 4);
+    assert!(visited.is_visited(0, 4));
+    assert!(!visited.is_visited(1, 4));
+    assert!(!visited.is_visited(2, 4));
+    assert!(!visited.set_and_get_visited(1, 4));
+    assert!(visited.set_and_get_visited(1, 4));
+    assert!(visited.is_visited(0, 4));
+    assert!(!visited.is_visited(0, 5));
+    assert!(!visited.is_visited(2, 4));
+}
+
+#[test]
+fn test_meet() {
+    let mut visited = MultiVisited::<u8, 2>::zero(10);
+    visited.set_visited(0, 2);
+    assert!(!visited.meet(2));
+    visited.set_visited(1, 3);
+    assert!(!visited.meet(2));
+    assert!(!visited.meet(3));
+    visited.set_visited(1, 2);
+    assert!(visited.meet(2));
+    visited.clear();
+    assert!(!visited.meet(2));
+}
+
+#[test]
+fn test_clear_across_wrap_around() {
+    let len = 10;
+    let mut visited = MultiVisited::<u8, 2>::zero(len);
+    for epoch in 0..600 {
+        for index in 0..len {
+            assert!(!visited.is_visited(0, index), "epoch {epoch}");
+            assert!(!visited.is_visited(1, index), "epoch {epoch}");
+        }
+        visited.set_visited(0, epoch % len);
+        visited.set_visited(1, (epoch * 3) % len);
+        assert!(visited.is_visited(0, epoch % len));
+        assert!(visited.is_visited(1, (epoch * 3) % len));
+        assert_eq!(visited.meet(epoch % len), epoch % len == (epoch * 3) % len);
+        visited.clear();
+    }
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::Lcg;
+use visited_rs::prelude::*;
+
+#[test]
+fn test_recent_history_survives_rebases() {
+    let len = 20;
+    let history = 5;
+    let mut random = Lcg(17);
+    let mut recent = RecencyVisited::<u8>::with_history(len, history);
+    assert_eq!(recent.history(), history);
+    // Epoch of the last visit of each index, as a reference.
+    let mut last_visits: Vec<Option<u64>> = vec![None; len];
+    // About twenty rebases, as each one leaves `u8::MAX - history` epochs.
+    for round in 0..5000_u64 {
+        let epoch = recent.epoch();
+        assert_eq!(epoch, round + 1);
+        for _ in 0..random.next(4) {
+            let index = random.next(len);
+            assert_eq!(
+                recent.set_and_get_visited(index),
+                last_visits[index] == Some(epoch)
+            );
+            last_visits[index] = Some(epoch);
+        }
+        for (index, last_visit) in last_visits.iter().enumerate() {
+            assert_eq!(recent.is_visited(index), *last_visit == Some(epoch));
+            let Some(last_visit) = *last_visit else {
+                assert_eq!(recent.last_visit_epoch(index), None);
+                assert!(!recent.visited_within(index, history + 1));
+                continue;
+            };
+            let age = (epoch - last_visit) as usize;
+            if age <= history {
+                assert_eq!(
+                    recent.last_visit_epoch(index),
+                    Some(last_visit),
+                    "round {round}, index {index}"
+                );
+                assert!(!recent.visited_within(index, age), "round {round}");
+                assert!(recent.visited_within(index, age + 1), "round {round}");
+            } else {
+                // Older visits may be forgotten, but never misreported.
+                if let Some(visit) = recent.last_visit_epoch(index) {
+                    assert_eq!(visit, last_visit, "round {round}, index {index}");
+                }
+                assert!(!recent.visited_within(index, history + 1), "round {round}");
+            }
+        }
+        recent.clear();
+    }
+}
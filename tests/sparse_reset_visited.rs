@@ -0,0 +1,40 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::Lcg;
+use visited_rs::prelude::*;
+
+#[test]
+fn test_sparse_reset_matches_vec_bool() {
+    // With blocks of 16 values, the dirty bitmap spans two words, and the
+    // last block is only partially filled.
+    let len = 64 * 16 + 1000 + 5;
+    let mut random = Lcg(5);
+    let mut visited = SparseResetVisited::<u8>::with_block_size(len, 16);
+    let mut expected = vec![false; len];
+    for round in 0..800 {
+        // Each round writes a few clusters, so that most blocks stay clean.
+        for _ in 0..random.next(4) {
+            let start = random.next(len);
+            let end = (start + random.next(40)).min(len);
+            for (index, expected) in expected.iter_mut().enumerate().take(end).skip(start) {
+                if random.next(2) == 0 {
+                    visited.set_visited(index);
+                } else {
+                    assert_eq!(visited.set_and_get_visited(index), *expected);
+                }
+                *expected = true;
+            }
+        }
+        for (index, expected) in expected.iter().enumerate() {
+            assert_eq!(
+                visited.is_visited(index),
+                *expected,
+                "round {round}, index {index}"
+            );
+        }
+        visited.clear();
+        expected.fill(false);
+    }
+}
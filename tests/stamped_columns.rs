@@ -0,0 +1,85 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+use visited_rs::prelude::*;
+
+#[derive(Clone, Debug, Default, PartialEq, Stamped)]
+struct Brandes {
+    distance: u32,
+    sigma: f64,
+    delta: f64,
+    predecessors: Vec<usize>,
+}
+
+#[test]
+fn test_accessors() {
+    let mut state = StampedColumns::<BrandesColumns, u8>::zero(10);
+    assert_eq!(state.len(), 10);
+    assert_eq!(state.distance(3), None);
+    assert_eq!(state.get(3), None);
+    state.set_distance(3, 5);
+    // Writing a field initializes the other fields of the row to their defaults.
+    assert_eq!(state.distance(3), Some(&5));
+    assert_eq!(state.sigma(3), Some(&0.0));
+    assert_eq!(state.predecessors(3), Some(&Vec::new()));
+    *state.sigma_mut(3) += 2.0;
+    state.predecessors_mut(3).push(1);
+    assert_eq!(
+        state.get(3),
+        Some(Brandes {
+            distance: 5,
+            sigma: 2.0,
+            delta: 0.0,
+            predecessors: vec![1],
+        })
+    );
+    assert!(state.contains(3));
+    assert!(!state.contains(4));
+    assert_eq!(state.delta(4), None);
+    state.insert(
+        4,
+        Brandes {
+            distance: 1,
+            sigma: 1.0,
+            delta: 0.5,
+            predecessors: vec![3],
+        },
+    );
+    assert_eq!(state.delta(4), Some(&0.5));
+    assert!(format!("{:?}", state).contains("BrandesColumns"));
+}
+
+#[test]
+fn test_clear_hides_and_resets_stale_rows() {
+    let mut state = StampedColumns::<BrandesColumns, u8>::zero(10);
+    state.set_sigma(4, 2.0);
+    state.set_delta(4, 3.0);
+    state.predecessors_mut(4).push(7);
+    state.clear();
+    assert_eq!(state.sigma(4), None);
+    assert_eq!(state.delta(4), None);
+    assert_eq!(state.get(4), None);
+    // The stale values are still in the columns, but are not read back.
+    assert_eq!(state.columns().sigma[4], 2.0);
+    *state.sigma_mut(4) += 1.0;
+    assert_eq!(state.sigma(4), Some(&1.0));
+    assert_eq!(state.delta(4), Some(&0.0));
+    assert_eq!(state.predecessors(4), Some(&Vec::new()));
+}
+
+#[test]
+fn test_wrap_around() {
+    let len = 7;
+    let mut state = StampedColumns::<BrandesColumns, u8>::zero(len);
+    for epoch in 0..600_u32 {
+        for index in 0..len {
+            assert_eq!(state.distance(index), None, "epoch {epoch}");
+        }
+        let index = epoch as usize % len;
+        state.set_distance(index, epoch);
+        *state.delta_mut(index) += 1.0;
+        assert_eq!(state.distance(index), Some(&epoch));
+        assert_eq!(state.delta(index), Some(&1.0));
+        assert_eq!(state.sigma((index + 1) % len), None);
+        state.clear();
+    }
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "alloc")]
+
+use std::{
+    cell::Cell,
+    panic::{catch_unwind, AssertUnwindSafe},
+    rc::Rc,
+};
+
+use visited_rs::prelude::*;
+
+/// Value counting how many times it was dropped, optionally panicking when dropped.
+struct Tracked {
+    drops: Rc<Cell<usize>>,
+    panics: bool,
+}
+
+impl Tracked {
+    fn new(drops: &Rc<Cell<usize>>) -> Self {
+        Self {
+            drops: drops.clone(),
+            panics: false,
+        }
+    }
+
+    fn panicking(drops: &Rc<Cell<usize>>) -> Self {
+        Self {
+            drops: drops.clone(),
+            panics: true,
+        }
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+        if self.panics {
+            panic!("dropping a panicking value");
+        }
+    }
+}
+
+#[test]
+fn test_insert_and_get() {
+    let mut values = StampedVec::<u32, u8>::zero(10);
+    assert_eq!(values.len(), 10);
+    assert!(!values.contains(3));
+    assert_eq!(values.get(3), None);
+    assert_eq!(values.insert(3, 7), None);
+    assert!(values.contains(3));
+    assert_eq!(values.get(3), Some(&7));
+    assert_eq!(values.insert(3, 8), Some(7));
+    *values.get_mut(3).unwrap() += 1;
+    assert_eq!(values.get(3), Some(&9));
+    assert_eq!(values.get_mut(4), None);
+}
+
+#[test]
+fn test_entry() {
+    let mut values = StampedVec::<u32, u8>::zero(10);
+    assert!(matches!(values.entry(2), Entry::Vacant(_)));
+    assert_eq!(values.entry(2).index(), 2);
+    *values.entry(2).or_insert(1) += 10;
+    assert_eq!(values.get(2), Some(&11));
+    values.entry(2).and_modify(|value| *value *= 2).or_default();
+    assert_eq!(values.get(2), Some(&22));
+    values.entry(5).and_modify(|value| *value *= 2).or_default();
+    assert_eq!(values.get(5), Some(&0));
+    assert_eq!(*values.get_or_insert_with(6, || 4), 4);
+    assert_eq!(*values.get_or_insert_with(6, || 5), 4);
+    if let Entry::Occupied(mut entry) = values.entry(6) {
+        assert_eq!(entry.insert(3), 4);
+        assert_eq!(*entry.get(), 3);
+    } else {
+        panic!("the entry should be occupied");
+    }
+}
+
+#[test]
+fn test_clear_and_wrap_around() {
+    let mut values = StampedVec::<usize, u8>::zero(4);
+    for epoch in 0..600 {
+        assert_eq!(values.iter().count(), 0);
+        assert_eq!(values.insert(epoch % 4, epoch), None);
+        assert_eq!(values.get(epoch % 4), Some(&epoch));
+        assert_eq!(values.get((epoch + 1) % 4), None);
+        values.clear();
+        assert!(!values.contains(epoch % 4));
+    }
+    // Clear until right before the epoch wraps around, so that it does on the last clear.
+    let mut values = StampedVec::<u8, u8>::zero(2);
+    values.insert(0, 1);
+    for _ in 1..u8::MAX {
+        values.clear();
+    }
+    assert_eq!(values.get(0), None);
+    values.insert(1, 2);
+    values.clear();
+    assert_eq!(values.get(0), None);
+    assert_eq!(values.get(1), None);
+    assert_eq!(values.insert(1, 3), None);
+    assert_eq!(values.get(1), Some(&3));
+}
+
+#[test]
+fn test_iter() {
+    let mut values = StampedVec::<char, u8>::zero(6);
+    values.insert(4, 'a');
+    values.insert(1, 'b');
+    values.clear();
+    values.insert(5, 'c');
+    values.insert(1, 'd');
+    assert_eq!(
+        values.iter().collect::<Vec<_>>(),
+        vec![(1, &'d'), (5, &'c')]
+    );
+}
+
+#[test]
+fn test_values_are_dropped_exactly_once() {
+    let drops = Rc::new(Cell::new(0));
+    let mut values = StampedVec::<Tracked, u8>::zero(8);
+    for epoch in 0..600 {
+        values.insert(epoch % 8, Tracked::new(&drops));
+        values.insert((epoch * 3) % 8, Tracked::new(&drops));
+        values.clear();
+    }
+    // At most one stale value per index is still alive.
+    let alive = 1200 - drops.get();
+    assert!(alive <= 8);
+    drop(values);
+    assert_eq!(drops.get(), 1200);
+}
+
+#[test]
+fn test_panicking_drop_is_not_repeated() {
+    let drops = Rc::new(Cell::new(0));
+    let panicking_drops = Rc::new(Cell::new(0));
+    let mut values = StampedVec::<Tracked, u8>::zero(4);
+    values.insert(1, Tracked::panicking(&panicking_drops));
+    values.clear();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        values.insert(1, Tracked::new(&drops));
+    }));
+    assert!(result.is_err());
+    assert_eq!(panicking_drops.get(), 1);
+    // The value being written is dropped while unwinding, and the index is left empty.
+    assert_eq!(drops.get(), 1);
+    assert_eq!(values.get(1).map(|_| ()), None);
+    values.insert(1, Tracked::new(&drops));
+    drop(values);
+    assert_eq!(panicking_drops.get(), 1);
+    assert_eq!(drops.get(), 2);
+}
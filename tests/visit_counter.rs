@@ -0,0 +1,70 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::Lcg;
+use visited_rs::prelude::*;
+
+#[test]
+fn test_counts_match_reference_across_clears() {
+    let len = 50;
+    let mut random = Lcg(29);
+    let mut counter = VisitCounter::<u8, u32>::zero(len);
+    let mut expected = vec![0_u32; len];
+    // More than twice the number of epochs, so that the stamps wrap around.
+    for round in 0..600 {
+        for _ in 0..random.next(80) {
+            let index = random.next(len);
+            expected[index] += 1;
+            assert_eq!(counter.increment(index), expected[index]);
+        }
+        for (index, expected) in expected.iter().enumerate() {
+            assert_eq!(counter.count(index), *expected, "round {round}");
+        }
+        assert_eq!(
+            counter.number_of_visited(),
+            expected.iter().filter(|count| **count > 0).count()
+        );
+        counter.clear();
+        expected.fill(0);
+        assert_eq!(counter.number_of_visited(), 0);
+        assert!((0..len).all(|index| counter.count(index) == 0));
+    }
+}
+
+#[test]
+fn test_increment_saturates() {
+    let mut counter = VisitCounter::<u8, u8>::zero(4);
+    for _ in 0..300 {
+        counter.increment(1);
+    }
+    counter.increment(2);
+    assert_eq!(counter.count(1), u8::MAX);
+    assert_eq!(counter.increment(1), u8::MAX);
+    assert_eq!(counter.top_k(1), vec![(1, u8::MAX)]);
+    counter.clear();
+    assert_eq!(counter.count(1), 0);
+    assert_eq!(counter.increment(1), 1);
+}
+
+#[test]
+fn test_top_k() {
+    let mut counter = VisitCounter::<u8, u32>::zero(10);
+    assert!(counter.top_k(3).is_empty());
+    for (index, visits) in [(7, 2), (3, 5), (1, 2), (9, 1), (4, 5), (2, 2)] {
+        for _ in 0..visits {
+            counter.increment(index);
+        }
+    }
+    assert!(counter.top_k(0).is_empty());
+    // Ties are broken by increasing index.
+    assert_eq!(counter.top_k(1), vec![(3, 5)]);
+    assert_eq!(counter.top_k(2), vec![(3, 5), (4, 5)]);
+    assert_eq!(counter.top_k(4), vec![(3, 5), (4, 5), (1, 2), (2, 2)]);
+    let all = vec![(3, 5), (4, 5), (1, 2), (2, 2), (7, 2), (9, 1)];
+    assert_eq!(counter.number_of_visited(), all.len());
+    assert_eq!(counter.top_k(all.len()), all);
+    assert_eq!(counter.top_k(100), all);
+    counter.clear();
+    assert!(counter.top_k(100).is_empty());
+}
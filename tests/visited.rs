@@ -0,0 +1,253 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::Lcg;
+use visited_rs::prelude::*;
+
+#[test]
+fn test_incremental_resize_mid_sweep() {
+    let mut visited = Visited::<u8>::zero(255);
+    visited.set_reset_strategy(ResetStrategy::Incremental);
+    visited.clear();
+    visited.set_visited(0);
+    for _ in 0..200 {
+        visited.clear();
+    }
+    visited.resize(25500);
+    for clears in 0..1000 {
+        assert!(
+            !visited.is_visited(0),
+            "index 0 visited after {clears} clears"
+        );
+        visited.clear();
+    }
+}
+
+#[test]
+fn test_incremental_matches_vec_bool() {
+    for seed in 0..8 {
+        let mut random = Lcg(seed);
+        let mut visited = Visited::<u8>::zero(random.next(300));
+        visited.set_reset_strategy(ResetStrategy::Incremental);
+        let mut expected = vec![false; visited.len()];
+        for round in 0..3000 {
+            match random.next(100) {
+                0 => {
+                    let new_len = random.next(2000);
+                    visited.resize(new_len);
+                    expected.resize(new_len, false);
+                }
+                1..=5 => {
+                    visited.push_unvisited();
+                    expected.push(false);
+                }
+                6 => {
+                    let len = random.next(expected.len() + 1);
+                    visited.truncate(len);
+                    expected.truncate(len);
+                }
+                _ => {}
+            }
+            for _ in 0..random.next(8) {
+                if expected.is_empty() {
+                    break;
+                }
+                let index = random.next(expected.len());
+                assert_eq!(visited.set_and_get_visited(index), expected[index]);
+                expected[index] = true;
+            }
+            for (index, expected) in expected.iter().enumerate() {
+                assert_eq!(
+                    visited.is_visited(index),
+                    *expected,
+                    "seed {seed}, round {round}, index {index}"
+                );
+            }
+            visited.clear();
+            expected.fill(false);
+        }
+    }
+}
+
+#[test]
+fn test_checked_access_reports_invalid_indices() {
+    let mut visited = Visited::<u8>::zero(10);
+    assert_eq!(
+        visited.try_is_visited(-1_i32),
+        Err(VisitedError::LossyIndexConversion)
+    );
+    assert_eq!(
+        visited.try_set_visited(i64::MIN),
+        Err(VisitedError::LossyIndexConversion)
+    );
+    assert_eq!(
+        visited.try_set_and_get_visited(-3_i8),
+        Err(VisitedError::LossyIndexConversion)
+    );
+    assert_eq!(
+        visited.try_is_visited(10_u32),
+        Err(VisitedError::IndexOutOfBounds { index: 10, len: 10 })
+    );
+    assert_eq!(
+        visited.try_set_visited(u128::from(u64::MAX) + 1),
+        Err(VisitedError::LossyIndexConversion)
+    );
+    assert_eq!(
+        visited.try_set_and_get_visited(usize::MAX),
+        Err(VisitedError::IndexOutOfBounds {
+            index: usize::MAX,
+            len: 10
+        })
+    );
+    // The failed calls left the visited struct untouched.
+    assert_eq!(visited.count_visited(..), 0);
+
+    assert_eq!(visited.try_set_and_get_visited(9_i16), Ok(false));
+    assert_eq!(visited.try_set_and_get_visited(9_u64), Ok(true));
+    assert_eq!(visited.try_set_visited(0_isize), Ok(()));
+    assert_eq!(visited.try_is_visited(0_u8), Ok(true));
+    assert_eq!(visited.try_is_visited(1_i32), Ok(false));
+    visited.clear();
+    assert_eq!(visited.try_is_visited(9_i32), Ok(false));
+}
+
+#[test]
+fn test_unchecked_access_matches_checked_access() {
+    let mut checked = Visited::<u8>::zero(100);
+    let mut unchecked = Visited::<u8>::zero(100);
+    let mut random = Lcg(3);
+    for _ in 0..600 {
+        for _ in 0..random.next(50) {
+            let index = random.next(100);
+            // SAFETY: the index is smaller than the length of the visited struct.
+            unsafe {
+                assert_eq!(
+                    unchecked.is_visited_unchecked(index),
+                    checked.is_visited(index)
+                );
+                assert_eq!(
+                    unchecked.set_and_get_visited_unchecked(index),
+                    checked.set_and_get_visited(index)
+                );
+                unchecked.set_visited_unchecked((index * 7) % 100);
+            }
+            checked.set_visited((index * 7) % 100);
+        }
+        for index in 0..100 {
+            assert_eq!(unchecked.is_visited(index), checked.is_visited(index));
+        }
+        checked.clear();
+        unchecked.clear();
+    }
+}
+
+#[test]
+fn test_unvisited_matches_vec_bool() {
+    for reset_strategy in [ResetStrategy::Full, ResetStrategy::Incremental] {
+        let mut random = Lcg(11);
+        let mut visited = Visited::<u8>::zero(300);
+        visited.set_reset_strategy(reset_strategy);
+        let mut expected = vec![false; 300];
+        for round in 0..1000 {
+            for _ in 0..random.next(60) {
+                let index = random.next(300);
+                match random.next(4) {
+                    0 => {
+                        visited.set_unvisited(index);
+                        expected[index] = false;
+                    }
+                    1 => {
+                        assert_eq!(visited.set_and_get_unvisited(index), expected[index]);
+                        expected[index] = false;
+                    }
+                    2 => {
+                        visited.set_visited(index);
+                        expected[index] = true;
+                    }
+                    _ => {
+                        assert_eq!(visited.set_and_get_visited(index), expected[index]);
+                        expected[index] = true;
+                    }
+                }
+            }
+            for (index, expected) in expected.iter().enumerate() {
+                assert_eq!(
+                    visited.is_visited(index),
+                    *expected,
+                    "{reset_strategy:?}, round {round}, index {index}"
+                );
+            }
+            visited.clear();
+            expected.fill(false);
+        }
+    }
+}
+
+#[test]
+fn test_range_operations_match_vec_bool() {
+    for len in [0, 1, 63, 64, 65, 200] {
+        let mut random = Lcg(13);
+        let mut visited = Visited::<u8>::zero(len);
+        let mut expected = vec![false; len];
+        for round in 0..300 {
+            for _ in 0..random.next(6) {
+                // Empty ranges included, as the end may equal the start.
+                let start = random.next(len + 1);
+                let end = start + random.next(len + 1 - start);
+                if random.next(3) == 0 && start < len {
+                    visited.set_unvisited(start);
+                    expected[start] = false;
+                } else {
+                    visited.set_range_visited(start..end);
+                    expected[start..end].fill(true);
+                }
+            }
+            for _ in 0..20 {
+                let start = random.next(len + 1);
+                let end = start + random.next(len + 1 - start);
+                let values = &expected[start..end];
+                let context = format!("len {len}, round {round}, range {start}..{end}");
+                assert_eq!(
+                    visited.all_visited(start..end),
+                    values.iter().all(|value| *value),
+                    "{context}"
+                );
+                assert_eq!(
+                    visited.any_visited(start..end),
+                    values.iter().any(|value| *value),
+                    "{context}"
+                );
+                assert_eq!(
+                    visited.count_visited(start..end),
+                    values.iter().filter(|value| **value).count(),
+                    "{context}"
+                );
+            }
+            assert_eq!(
+                visited.count_visited(..),
+                expected.iter().filter(|value| **value).count()
+            );
+            // Starting points past the end must find nothing.
+            for from in 0..len + 2 {
+                let scan = expected.get(from..).unwrap_or(&[]);
+                assert_eq!(
+                    visited.first_unvisited(from),
+                    scan.iter()
+                        .position(|value| !*value)
+                        .map(|index| from + index),
+                    "len {len}, round {round}, from {from}"
+                );
+                assert_eq!(
+                    visited.next_visited(from),
+                    scan.iter()
+                        .position(|value| *value)
+                        .map(|index| from + index),
+                    "len {len}, round {round}, from {from}"
+                );
+            }
+            visited.clear();
+            expected.fill(false);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+#![cfg(all(feature = "alloc", feature = "derive"))]
+
+use visited_rs::prelude::*;
+
+#[derive(Clone, Copy, VisitedIndex)]
+struct NodeId(u32);
+
+#[derive(Clone, Copy, VisitedIndex)]
+struct EdgeId {
+    id: i16,
+}
+
+#[derive(Clone, Copy, VisitedIndex)]
+struct Wrapper<I>(I);
+
+#[test]
+fn test_tuple_newtype() {
+    let mut visited = Visited::<u8>::zero(10);
+    assert!(!visited.set_and_get_visited(NodeId(3)));
+    assert!(visited.set_and_get_visited(NodeId(3)));
+    assert_eq!(visited.try_is_visited(NodeId(3)), Ok(true));
+    assert_eq!(visited.try_is_visited(NodeId(4)), Ok(false));
+    assert_eq!(
+        visited.try_is_visited(NodeId(10)),
+        Err(VisitedError::IndexOutOfBounds { index: 10, len: 10 })
+    );
+}
+
+#[test]
+fn test_named_newtype() {
+    let mut visited = Visited::<u8>::zero(10);
+    assert!(!visited.set_and_get_visited(EdgeId { id: 7 }));
+    assert!(visited.set_and_get_visited(EdgeId { id: 7 }));
+    assert_eq!(visited.try_is_visited(EdgeId { id: 7 }), Ok(true));
+    assert_eq!(
+        visited.try_is_visited(EdgeId { id: -1 }),
+        Err(VisitedError::LossyIndexConversion)
+    );
+}
+
+#[test]
+fn test_generic_wrapper() {
+    let mut visited = Visited::<u8>::zero(10);
+    assert!(!visited.set_and_get_visited(Wrapper(2_u64)));
+    assert!(visited.set_and_get_visited(Wrapper(2_usize)));
+    assert!(!visited.set_and_get_visited(Wrapper(NodeId(5))));
+    assert_eq!(visited.try_is_visited(Wrapper(NodeId(5))), Ok(true));
+    assert_eq!(visited.try_is_visited(Wrapper(EdgeId { id: 2 })), Ok(true));
+    assert_eq!(
+        visited.try_is_visited(Wrapper(-2_i64)),
+        Err(VisitedError::LossyIndexConversion)
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "alloc")]
+
+use std::collections::hash_map::RandomState;
+
+use visited_rs::prelude::*;
+
+/// Marks a fixed pattern of indices over a few epochs, checking each answer against a `Vec<bool>`.
+fn check_marking<V: VisitedSet>(visited: &mut V, len: usize) {
+    let mut expected = vec![false; len];
+    for epoch in 0..600 {
+        for step in 0..len {
+            let index = (step * 7 + epoch) % len;
+            assert_eq!(visited.is_visited(index), expected[index]);
+            if step % 3 == 0 {
+                assert_eq!(visited.set_and_get_visited(index), expected[index]);
+                expected[index] = true;
+            } else if step % 3 == 1 {
+                visited.set_visited(index);
+                expected[index] = true;
+            }
+        }
+        visited.clear();
+        expected.fill(false);
+    }
+}
+
+#[test]
+fn test_marking_is_consistent_across_visited_sets() {
+    let len = 100;
+    check_marking(&mut Visited::<u8>::zero(len), len);
+    check_marking(&mut BitVisited::<u8>::zero(len), len);
+    check_marking(&mut SparseResetVisited::<u8>::zero(len), len);
+    check_marking(&mut TrackedVisited::<u8>::zero(len), len);
+    check_marking(&mut AtomicVisited::<u8>::zero(len), len);
+    check_marking(&mut ConcurrentVisited::<u8>::zero(len), len);
+    check_marking(&mut RecencyVisited::<u8>::zero(len), len);
+    let clock = EpochClock::<u8>::new();
+    check_marking(&mut ClockedVisited::zero(&clock, len), len);
+    check_marking(
+        &mut HashVisited::<usize, u8, _>::with_hasher(RandomState::new()),
+        len,
+    );
+    check_marking(&mut vec![false; len], len);
+}
+
+#[test]
+fn test_len_and_capacity_agree_with_inherent_methods() {
+    let mut visited = Visited::<u8>::zero(10);
+    visited.reserve(100);
+    assert_eq!(VisitedSet::len(&visited), Some(visited.len()));
+    assert_eq!(VisitedSet::capacity(&visited), visited.capacity());
+    assert!(VisitedSet::capacity(&visited) >= 110);
+
+    let mut tracked = TrackedVisited::<u8>::zero(10);
+    tracked.set_visited(3);
+    assert_eq!(VisitedSet::len(&tracked), Some(10));
+    assert_eq!(VisitedSet::capacity(&tracked), 10);
+    // The inherent length counts the visited values, as for `HashSet::len`.
+    assert_eq!(tracked.len(), 1);
+    assert!(!tracked.is_empty());
+
+    let mut hashed = HashVisited::<usize, u8, _>::with_hasher(RandomState::new());
+    hashed.set_visited(1 << 40);
+    assert_eq!(VisitedSet::len(&hashed), None);
+    assert!(!VisitedSet::is_empty(&hashed));
+    assert_eq!(VisitedSet::capacity(&hashed), hashed.capacity());
+    assert_eq!(hashed.len(), 1);
+
+    let array: ArrayVisited<u8, 16> = ArrayVisited::new();
+    assert_eq!(VisitedSet::len(&array), Some(16));
+    assert_eq!(VisitedSet::capacity(&array), 16);
+}
@@ -0,0 +1,224 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, Member};
+
+#[proc_macro_derive(VisitedIndex)]
+/// Derives `VisitedIndex` for structs wrapping a single index, such as `struct NodeId(u32)`.
+///
+/// The derived implementation delegates to the `VisitedIndex` implementation of the wrapped field.
+pub fn derive_visited_index(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_visited_index(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_visited_index(mut input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "VisitedIndex can only be derived for structs",
+            ))
+        }
+    };
+
+    let field = match fields {
+        Fields::Named(fields) if fields.named.len() == 1 => &fields.named[0],
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "VisitedIndex can only be derived for structs with exactly one field",
+            ))
+        }
+    };
+
+    let member = field
+        .ident
+        .clone()
+        .map_or_else(|| Member::from(0), Member::Named);
+    let field_type = field.ty.clone();
+
+    input
+        .generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#field_type: ::visited_rs::prelude::VisitedIndex));
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::visited_rs::prelude::VisitedIndex for #name #type_generics #where_clause {
+            #[inline(always)]
+            fn into_index(self) -> usize {
+                ::visited_rs::prelude::VisitedIndex::into_index(self.#member)
+            }
+
+            #[inline(always)]
+            fn try_into_index(self) -> ::core::option::Option<usize> {
+                ::visited_rs::prelude::VisitedIndex::try_into_index(self.#member)
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(Stamped)]
+/// Derives the columnar storage of a row struct, to be used with `StampedColumns`.
+///
+/// For a struct `Row` with named fields, this generates:
+///
+/// * a `RowColumns` struct with a public vector for each field, implementing `Columns`;
+/// * a `RowStamped` trait, implemented by `StampedColumns<RowColumns, T>`, providing
+///   for each `field` the `field`, `field_mut` and `set_field` accessors.
+///
+/// All the fields must implement `Default`, `Clone` and `Debug`.
+pub fn derive_stamped(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_stamped(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_stamped(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "Stamped can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Stamped can only be derived for structs",
+            ))
+        }
+    };
+
+    if fields.is_empty() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Stamped can only be derived for structs with at least one field",
+        ));
+    }
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "Stamped cannot be derived for generic structs",
+        ));
+    }
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let columns = format_ident!("{}Columns", name);
+    let stamped = format_ident!("{}Stamped", name);
+    let names: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let first = names[0];
+    let names_mut: Vec<_> = names
+        .iter()
+        .map(|name| format_ident!("{}_mut", name.as_ref().unwrap()))
+        .collect();
+    let names_set: Vec<_> = names
+        .iter()
+        .map(|name| format_ident!("set_{}", name.as_ref().unwrap()))
+        .collect();
+
+    let field_docs: Vec<_> = names
+        .iter()
+        .map(|name| format!("Column of the `{}` field.", name.as_ref().unwrap()))
+        .collect();
+    let columns_doc = format!(
+        "Columnar storage of [`{}`], with a vector for each field.",
+        name
+    );
+    let stamped_doc = format!(
+        "Accessors to the fields of [`{}`] stored in stamped columns.",
+        name
+    );
+
+    Ok(quote! {
+        #[derive(Clone, Debug)]
+        #[doc = #columns_doc]
+        #vis struct #columns {
+            #(
+                #[doc = #field_docs]
+                pub #names: ::visited_rs::__private::Vec<#types>,
+            )*
+        }
+
+        impl ::visited_rs::prelude::Columns for #columns {
+            type Row = #name;
+
+            fn with_len(len: usize) -> Self {
+                Self {
+                    #(
+                        #names: ::core::iter::repeat_with(<#types as ::core::default::Default>::default)
+                            .take(len)
+                            .collect(),
+                    )*
+                }
+            }
+
+            #[inline(always)]
+            fn len(&self) -> usize {
+                self.#first.len()
+            }
+
+            #[inline(always)]
+            fn reset_row(&mut self, index: usize) {
+                #(
+                    self.#names[index] = ::core::default::Default::default();
+                )*
+            }
+
+            #[inline(always)]
+            fn write_row(&mut self, index: usize, row: Self::Row) {
+                #(
+                    self.#names[index] = row.#names;
+                )*
+            }
+
+            #[inline(always)]
+            fn read_row(&self, index: usize) -> Self::Row {
+                #name {
+                    #(
+                        #names: ::core::clone::Clone::clone(&self.#names[index]),
+                    )*
+                }
+            }
+        }
+
+        #[doc = #stamped_doc]
+        #vis trait #stamped: ::visited_rs::prelude::ColumnAccess<Columns = #columns> {
+            #(
+                #[inline(always)]
+                /// Returns a reference to the field, if its row was written in the current epoch.
+                fn #names<U: ::visited_rs::prelude::VisitedIndex>(&self, index: U) -> ::core::option::Option<&#types> {
+                    self.column(index, |columns| columns.#names.as_slice())
+                }
+
+                #[inline(always)]
+                /// Returns a mutable reference to the field, resetting its row if not written in the current epoch.
+                fn #names_mut<U: ::visited_rs::prelude::VisitedIndex>(&mut self, index: U) -> &mut #types {
+                    self.column_mut(index, |columns| columns.#names.as_mut_slice())
+                }
+
+                #[inline(always)]
+                /// Sets the field, resetting its row if not written in the current epoch.
+                fn #names_set<U: ::visited_rs::prelude::VisitedIndex>(&mut self, index: U, value: #types) {
+                    *self.#names_mut(index) = value;
+                }
+            )*
+        }
+
+        impl<S> #stamped for S where S: ::visited_rs::prelude::ColumnAccess<Columns = #columns> {}
+    })
+}